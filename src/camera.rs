@@ -0,0 +1,96 @@
+//! Which part of a hole is on screen. Holes can be any size, the camera keeps
+//! the ball in view while it rolls and can be panned around while aiming.
+use crate::common::*;
+use crate::geo::*;
+use crate::render::shapes::*;
+
+/// Fraction of the way to the ball the camera moves each physics update
+/// while following it.
+const FOLLOW_EASE: f64 = 0.1;
+/// Pixels per second the camera pans at.
+pub const PAN_SPEED: f64 = 320.0;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Camera {
+    /// Map pixel shown at the top left of the view.
+    pub position: Vec2<f64>,
+    /// Pixel size of the view.
+    pub size: Vec2<f64>,
+    /// Pixel size of the map being looked at.
+    pub bounds: Vec2<f64>,
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        let view = Vec2::new(WIDTH as f64, VIEW_HEIGHT as f64);
+        Self::new(view, view)
+    }
+}
+
+impl Camera {
+    /// A camera with a view of `size` on a map of `bounds`, looking at its
+    /// top left.
+    pub fn new(size: Vec2<f64>, bounds: Vec2<f64>) -> Self {
+        let mut camera = Self {
+            position: Vec2::new(0.0, 0.0),
+            size,
+            bounds,
+        };
+        camera.position = camera.clamp(camera.position);
+        camera
+    }
+
+    /// Centers the view on `center`, as far as the map allows.
+    pub fn look_at(&mut self, center: Vec2<f64>) {
+        self.position = self.clamp(center - self.size.scale(0.5));
+    }
+
+    /// Moves the view part of the way towards being centered on `center`.
+    pub fn follow(&mut self, center: Vec2<f64>) {
+        let target = self.clamp(center - self.size.scale(0.5));
+        self.position = self.position + (target - self.position).scale(FOLLOW_EASE);
+    }
+
+    /// Moves the view by `by` pixels, as far as the map allows.
+    pub fn pan(&mut self, by: Vec2<f64>) {
+        self.position = self.clamp(self.position + by);
+    }
+
+    /// The part of the screen the view is drawn in.
+    pub fn view(&self) -> Rect {
+        Rect::new(
+            Vec2::new(0, 0),
+            Vec2::new(self.size.x as i64, self.size.y as i64),
+        )
+    }
+
+    /// Whole pixels to take off map positions to draw them.
+    pub fn offset(&self) -> (i64, i64) {
+        (
+            self.position.x.round() as i64,
+            self.position.y.round() as i64,
+        )
+    }
+
+    /// Where the map pixel `at` is drawn on screen.
+    pub fn to_screen(&self, at: Vec2<f64>) -> (i64, i64) {
+        let (x, y) = self.offset();
+        (at.x.floor() as i64 - x, at.y.floor() as i64 - y)
+    }
+
+    /// Keeps the view on the map. A map smaller than the view is centered in
+    /// it instead.
+    fn clamp(&self, top_left: Vec2<f64>) -> Vec2<f64> {
+        let axis = |at: f64, size: f64, bounds: f64| {
+            if bounds <= size {
+                (bounds - size) / 2.0
+            } else {
+                at.clamp(0.0, bounds - size)
+            }
+        };
+        Vec2::new(
+            axis(top_left.x, self.size.x, self.bounds.x),
+            axis(top_left.y, self.size.y, self.bounds.y),
+        )
+    }
+}
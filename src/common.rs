@@ -1,4 +1,5 @@
 use std::rc::Rc;
+#[allow(dead_code)]
 pub(crate) type CachedSprite = (usize, usize, Rc<[u8]>);
 pub const BALL_WIDTH: usize = 10;
 pub const BALL_HEIGHT: usize = 8;
@@ -8,12 +9,45 @@ pub const BALL_HEIGHT: usize = 8;
 
 pub const WIDTH: usize = 640;
 pub const HEIGHT: usize = 400;
+/// Height of the part of the screen showing the hole. The strip below it is
+/// left for information.
+pub const VIEW_HEIGHT: usize = 368;
+
+/// Physics updates per second. The simulation always steps at this rate,
+/// whatever the display refresh rate is.
+pub const PHYSICS_HZ: u32 = 120;
+/// Seconds of game time covered by one physics update.
+pub const PHYSICS_DT: f64 = 1.0 / PHYSICS_HZ as f64;
 
 pub const TILE_SIZE: usize = 256;
 pub const WALL_TILE_ASSET: [u8; TILE_SIZE] = [1; TILE_SIZE];
 pub const GROUND_TILE_ASSET: [u8; TILE_SIZE] = [0; TILE_SIZE];
 pub const HOLE_TILE_ASSET: [u8; TILE_SIZE] = [3; TILE_SIZE];
 pub const PORTAL_TILE_ASSET: [u8; TILE_SIZE] = [2; TILE_SIZE];
+pub const SAND_TILE_ASSET: [u8; TILE_SIZE] = [4; TILE_SIZE];
+pub const WATER_TILE_ASSET: [u8; TILE_SIZE] = [5; TILE_SIZE];
+pub const VOID_TILE_ASSET: [u8; TILE_SIZE] = [6; TILE_SIZE];
+/// Palette entries for the uphill and downhill shading of slope tiles.
+pub const SLOPE_LIGHT: u8 = 7;
+pub const SLOPE_DARK: u8 = 8;
+/// Palette entries for bumpers, booster arrows and conveyor belts.
+pub const BUMPER_OUTER: u8 = 9;
+pub const BUMPER_INNER: u8 = 10;
+pub const BOOSTER_ARROW: u8 = 11;
+pub const CONVEYOR_BELT: u8 = 12;
+pub const CONVEYOR_SLAT: u8 = 13;
+/// Palette entries for gates and the switches that move them.
+pub const GATE: u8 = 14;
+pub const SWITCH: u8 = 15;
+
+pub const PLANET_COLOR: [u8; 4] = [0xc8, 0x6e, 0x3c, 0xff];
+pub const BLACK_HOLE_COLOR: [u8; 4] = [0x08, 0x04, 0x10, 0xff];
+pub const BLACK_HOLE_RIM_COLOR: [u8; 4] = [0x9b, 0x4d, 0xff, 0xff];
+pub const OBSTACLE_COLOR: [u8; 4] = [0xb0, 0x3a, 0x2e, 0xff];
+pub const OBSTACLE_HUB_COLOR: [u8; 4] = [0x5a, 0x32, 0x1e, 0xff];
+/// Colors of the set and clear pixels of the original monochrome screens.
+pub const MONO_FOREGROUND: [u8; 4] = [0x00, 0x00, 0x00, 0xff];
+pub const MONO_BACKGROUND: [u8; 4] = [0xff, 0xff, 0xff, 0xff];
 
 pub const BALL_SIZE: usize = 64;
 pub const BALL_ASSET: [u8; BALL_SIZE] = [
@@ -21,6 +55,7 @@ pub const BALL_ASSET: [u8; BALL_SIZE] = [
     1, 0, 0, 1, 1, 0, 0, 1, 0, 1, 1, 0, 0, 1, 1, 0, 0, 1, 1, 0, 0, 1, 1, 0, 0, 0, 0, 1, 1, 0, 0, 0,
 ];
 
+#[allow(dead_code)]
 #[derive(Debug, Eq, Hash, PartialEq, PartialOrd, Ord, Clone)]
 pub(crate) enum Frame {
     Ball,
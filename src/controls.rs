@@ -1,9 +1,13 @@
+use crate::geo::*;
+
 #[derive(Clone, Debug, Default)]
 pub struct Controls {
     pub aiming: Direction,
     pub power: PowerLevel,
     pub adj: AdjustmentType,
     pub hit: bool,
+    /// Which way to move the camera while aiming, -1, 0 or 1 on each axis.
+    pub pan: Vec2<i8>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -0,0 +1,113 @@
+use crate::map::level::*;
+use crate::map::map::*;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// The holes shipped with the game, in playing order.
+pub const STANDARD_HOLES: [&str; 4] = [
+    MAP1,
+    include_str!("../assets/maps/map2.txt"),
+    include_str!("../assets/maps/map3.txt"),
+    include_str!("../assets/maps/map4.txt"),
+];
+
+/// An ordered round of holes and the one currently being played.
+#[derive(Clone, Debug)]
+pub struct Course {
+    holes: Vec<Level>,
+    current: usize,
+}
+
+impl Default for Course {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl Course {
+    /// Creates a course from its holes. A course needs at least one hole.
+    pub fn new(holes: Vec<Level>) -> Course {
+        assert!(!holes.is_empty(), "a course needs at least one hole");
+        Course { holes, current: 0 }
+    }
+
+    /// The course shipped with the game.
+    pub fn standard() -> Course {
+        let holes = STANDARD_HOLES
+            .iter()
+            .map(|source| Level::parse(source).expect("bundled holes should parse"))
+            .collect();
+        Course::new(holes)
+    }
+
+    /// Loads every `.txt` level in `dir`, played in file name order.
+    pub fn load_dir<P: AsRef<Path>>(dir: P) -> Result<Course, LevelError> {
+        let mut paths = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "txt") {
+                paths.push(path);
+            }
+        }
+        if paths.is_empty() {
+            return Err(LevelError::Io(io::Error::new(
+                io::ErrorKind::NotFound,
+                "no .txt levels in course directory",
+            )));
+        }
+        paths.sort();
+        let holes = paths
+            .iter()
+            .map(Level::load)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Course::new(holes))
+    }
+
+    pub fn holes(&self) -> &[Level] {
+        &self.holes
+    }
+
+    /// The hole being played.
+    pub fn current(&self) -> &Level {
+        &self.holes[self.current]
+    }
+
+    /// The map of the hole being played.
+    pub fn map(&self) -> &GameMap {
+        &self.current().map
+    }
+
+    /// Index of the hole being played, counted from zero.
+    pub fn current_index(&self) -> usize {
+        self.current
+    }
+
+    /// 1-based number of the hole being played.
+    pub fn hole_number(&self) -> usize {
+        self.current + 1
+    }
+
+    pub fn hole_count(&self) -> usize {
+        self.holes.len()
+    }
+
+    pub fn is_last_hole(&self) -> bool {
+        self.current + 1 == self.holes.len()
+    }
+
+    /// Moves on to the next hole. Returns `false` when the last hole was
+    /// already being played.
+    pub fn advance(&mut self) -> bool {
+        if self.is_last_hole() {
+            return false;
+        }
+        self.current += 1;
+        true
+    }
+
+    /// Goes back to the first hole.
+    pub fn restart(&mut self) {
+        self.current = 0;
+    }
+}
@@ -1,4 +1,6 @@
-static SPLASH_SCREEN: [u64; 8000] = [
+/// The original 640x400 title screen, one 32 pixel word per entry. See
+/// `render::bitmap`.
+pub static SPLASH_SCREEN: [u64; 8000] = [
     0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
     0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
     0x00000000, 0x00000000, 0x1F800000, 0x3F000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
@@ -1001,7 +1003,8 @@ static SPLASH_SCREEN: [u64; 8000] = [
     0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF,
 ];
 
-const END_SCREEN: [u32; 8000] = [
+/// The original 640x400 screen shown after the last hole.
+pub static END_SCREEN: [u32; 8000] = [
     0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
     0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
     0x00000000, 0x00000000, 0x1F800000, 0x3F000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
@@ -2003,5 +2006,3 @@ const END_SCREEN: [u32; 8000] = [
     0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF,
     0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF,
 ];
-
-// pub fn u64_to_rgba(chunk: u64) -> [u8] {}
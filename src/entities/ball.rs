@@ -1,20 +1,47 @@
+use crate::camera::*;
 use crate::common::*;
 use crate::controls::AdjustmentType;
+use crate::entities::gravity::*;
+use crate::entities::obstacles::*;
+use crate::entities::portals::*;
 use crate::entities::tiles::*;
 use crate::geo::*;
 use crate::map::map::*;
+use crate::physics::*;
 use crate::render::helpers::*;
 use crate::render::sprites::*;
+use std::f64::consts::PI;
 
-static PI: f64 = 3.14159265359;
-const POWER_MAX: f64 = 10.3;
-const POWER_MIN: f64 = 0.7;
+/// Power can be raised while it stays under this.
+pub const POWER_MAX: f64 = 10.3;
+/// Power can be lowered while it stays over this.
+pub const POWER_MIN: f64 = 0.7;
+/// Speed in pixels per second below which a rolling ball starts coming to
+/// rest.
+pub const REST_SPEED: f64 = 15.0;
+/// Physics updates in a row the ball has to stay under `REST_SPEED` before it
+/// is stopped, a tenth of a second. A ball that only slows for a moment, such
+/// as one turning around under gravity, keeps rolling.
+pub const REST_TICKS: u32 = PHYSICS_HZ / 10;
+/// Most acceleration, in pixels per second squared, a ball can come to rest
+/// under. Steeper slopes and stronger gravity keep it rolling unless a wall
+/// holds it.
+pub const REST_ACCELERATION: f64 = 60.0;
+/// Pixels between the dots of the aiming guide.
+const AIM_DOT_SPACING: usize = 4;
+/// The aiming guide lengths offered in the settings, shortest first.
+pub const AIM_PREVIEWS: [(&str, AimPreview); 4] = [
+    ("OFF", AimPreview::off()),
+    ("SHORT", AimPreview::new(3 * PHYSICS_HZ as usize / 4, 1)),
+    ("NORMAL", AimPreview::new(3 * PHYSICS_HZ as usize / 2, 2)),
+    ("LONG", AimPreview::new(3 * PHYSICS_HZ as usize, 4)),
+];
 
 pub fn plan_ball_velocity(angle: BallAngle, power: BallPower) -> Vec2<f64> {
     let scalers = power.scalers();
     let x_vel = scalers * angle.xv();
     let y_vel = scalers * angle.yv();
-    return Vec2::new(x_vel, y_vel);
+    Vec2::new(x_vel, y_vel)
 }
 
 pub trait InputAdjustment {
@@ -54,10 +81,10 @@ where
     }
 }
 
+/// Velocity in pixels per second.
 #[derive(Clone, Debug)]
 pub struct BallVelocity {
     vec: Vec2<f64>,
-    friction: f64,
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -65,14 +92,24 @@ pub enum BallState {
     Rolling,
     Stopped,
 }
-#[derive(Copy, Clone, Debug)]
+/// The aiming guide, the centers the ball would pass through if hit now.
+#[derive(Clone, Debug)]
 pub struct AimLine {
-    p1: Point,
-    p2: Point,
+    points: Vec<Vec2<f64>>,
+}
+
+/// How far ahead the aiming guide looks. Shorter is harder.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AimPreview {
+    /// Most physics updates played out.
+    pub max_ticks: usize,
+    /// Bounces played out. The guide ends at the wall after them.
+    pub max_bounces: usize,
 }
 
 #[derive(Clone, Debug)]
 pub struct BallPower {
+    /// Launch speed in pixels per second for every four units of power.
     pub speed: f64,
     pub power: f64,
     pub min_power_step: f64,
@@ -102,38 +139,56 @@ impl MinMaxAdj for BallPower {
     }
 }
 
+impl Default for BallPower {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl BallPower {
     pub fn new() -> Self {
         Self {
-            speed: 0.90,
+            speed: 130.0,
             power: 10.0,
             min_power_step: 0.5,
             max_power_step: 1.0,
         }
     }
     pub fn scalers(self) -> f64 {
-        return (self.power / 4.0) * self.speed;
+        (self.power / 4.0) * self.speed
+    }
+    /// How far power is from `POWER_MIN` to `POWER_MAX`, from 0 to 1.
+    pub fn fraction(&self) -> f64 {
+        ((self.power - POWER_MIN) / (POWER_MAX - POWER_MIN)).clamp(0.0, 1.0)
     }
 }
 
+/// Aim direction. Turning is applied once per physics update while a key is
+/// held, so the turn speeds are in radians per second.
 #[derive(Clone, Debug)]
 pub struct BallAngle {
     pub theta: f64,
-    pub min_theta_step: f64,
-    pub max_theta_step: f64,
+    pub min_turn_speed: f64,
+    pub max_turn_speed: f64,
 }
 impl MinMaxAdj for BallAngle {
     fn min_increase(&mut self) {
-        self.theta += self.min_theta_step;
+        self.theta += self.min_turn_speed * PHYSICS_DT;
     }
     fn max_increase(&mut self) {
-        self.theta += self.max_theta_step;
+        self.theta += self.max_turn_speed * PHYSICS_DT;
     }
     fn min_decrease(&mut self) {
-        self.theta -= self.min_theta_step;
+        self.theta -= self.min_turn_speed * PHYSICS_DT;
     }
     fn max_decrease(&mut self) {
-        self.theta -= self.max_theta_step;
+        self.theta -= self.max_turn_speed * PHYSICS_DT;
+    }
+}
+
+impl Default for BallAngle {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -141,40 +196,54 @@ impl BallAngle {
     pub fn new() -> Self {
         Self {
             theta: 3.60,
-            min_theta_step: 0.01,
-            max_theta_step: 0.05,
+            // 0.01 and 0.05 radians a frame at the old 144 frames a second.
+            min_turn_speed: 1.44,
+            max_turn_speed: 7.2,
         }
     }
     pub fn xv(&self) -> f64 {
-        return (self.theta - PI / 2.0).sin();
+        (self.theta - PI / 2.0).sin()
     }
     pub fn yv(&self) -> f64 {
-        return (self.theta + PI / 2.0).cos();
+        (self.theta + PI / 2.0).cos()
+    }
+    /// Aim direction in degrees from 0 up to 360, counterclockwise on screen
+    /// from pointing right.
+    pub fn degrees(&self) -> f64 {
+        (-self.yv()).atan2(self.xv()).to_degrees().rem_euclid(360.0)
     }
 }
 #[derive(Clone, Debug)]
 pub struct Ball {
-    old_point: Point,
     point: Point,
     sprite: Sprite,
     pub velocity: BallVelocity,
     pub fpos: Vec2<f64>,
+    /// `fpos` before the last physics update, for drawing between updates.
+    prev_fpos: Vec2<f64>,
     pub power: BallPower,
     pub angle: BallAngle,
+    pub portal_cooldown: u32,
+    /// Physics updates in a row spent under `REST_SPEED`.
+    rest_ticks: u32,
+    /// Normal of the last wall touched, and the physics updates left before
+    /// it stops counting as holding the ball up.
+    support: Option<(Vec2<f64>, u32)>,
+    /// Whether the ball is on a booster it has already been sped up by.
+    boosted: bool,
 }
 
 impl BallVelocity {
     pub fn new(x: f64, y: f64) -> Self {
         Self {
             vec: Vec2::new(x, y),
-            friction: 0.9980,
         }
     }
     pub fn x(&self) -> f64 {
-        return self.vec.x;
+        self.vec.x
     }
     pub fn y(&self) -> f64 {
-        return self.vec.y;
+        self.vec.y
     }
     pub fn update(&mut self, vel: Vec2<f64>) {
         self.vec = vel;
@@ -189,34 +258,93 @@ impl BallVelocity {
         self.vec.x = 0.0;
         self.vec.y = 0.0;
     }
-    pub fn slow(&mut self) {
-        self.vec.x = self.vec.x * self.friction;
-        self.vec.y = self.vec.y * self.friction;
-        println!(
-            "SLOWWED Ball X Velocity: {} Ball Y Velocity: {}",
-            self.vec.x, self.vec.y
-        );
+    /// Applies `dt` seconds of rolling on a surface that keeps `friction`
+    /// of the ball's speed each second.
+    pub fn slow(&mut self, friction: f64, dt: f64) {
+        self.vec = self.vec.scale(friction.powf(dt));
+    }
+    /// Speed in pixels per second.
+    pub fn speed(&self) -> f64 {
+        self.vec.length()
     }
     pub fn velocity_below(&self, threshold: f64) -> bool {
-        return f64::abs(self.vec.x) + f64::abs(self.vec.y) < threshold;
+        self.speed() < threshold
     }
     pub fn to_map_point(self) -> Point {
-        return Point::new(self.vec.x as usize, self.vec.y as usize);
+        Point::new(self.vec.x as usize, self.vec.y as usize)
     }
     pub fn vec2(&self) -> Vec2<f64> {
-        return self.vec.clone();
+        self.vec
     }
 }
 
 impl AimLine {
-    pub fn new(x1: usize, y1: usize, x2: usize, y2: usize) -> Self {
-        let p1 = Point::new(x1, y1);
-        let p2 = Point::new(x2, y2);
-        Self { p1, p2 }
+    pub fn new(points: Vec<Vec2<f64>>) -> Self {
+        Self { points }
+    }
+
+    /// Sub-pixel ball centers, one per physics update.
+    pub fn points(&self) -> &[Vec2<f64>] {
+        &self.points
+    }
+
+    pub fn draw(&self, frame: &mut [u8], camera: &Camera) {
+        let points: Vec<(i64, i64)> = self
+            .points
+            .iter()
+            .map(|p| camera.to_screen(*p + Vec2::new(0.5, 0.5)))
+            .collect();
+        dotted_line(
+            frame,
+            &camera.view(),
+            &points,
+            AIM_DOT_SPACING,
+            [0xff, 0xff, 0xff, 0xff],
+        );
+    }
+}
+
+impl Default for AimPreview {
+    fn default() -> Self {
+        AIM_PREVIEWS[2].1
+    }
+}
+
+impl AimPreview {
+    pub const fn new(max_ticks: usize, max_bounces: usize) -> Self {
+        Self {
+            max_ticks,
+            max_bounces,
+        }
+    }
+
+    /// No aiming guide at all.
+    pub const fn off() -> Self {
+        Self::new(0, 0)
+    }
+
+    /// The name of this length in `AIM_PREVIEWS`, or `CUSTOM` for any other.
+    pub fn name(&self) -> &'static str {
+        AIM_PREVIEWS
+            .iter()
+            .find(|(_, preview)| preview == self)
+            .map_or("CUSTOM", |(name, _)| name)
     }
 
-    pub fn draw(&self, frame: &mut [u8]) {
-        line(frame, &self.p1, &self.p2, [0xff, 0xff, 0xff, 0xff]);
+    /// The next length in `AIM_PREVIEWS`, going round from the longest to
+    /// the shortest. Any other length goes to the shortest.
+    pub fn next(&self) -> Self {
+        let index = AIM_PREVIEWS
+            .iter()
+            .position(|(_, preview)| preview == self)
+            .map_or(0, |index| (index + 1) % AIM_PREVIEWS.len());
+        AIM_PREVIEWS[index].1
+    }
+}
+
+impl Default for Ball {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -225,7 +353,6 @@ impl Ball {
     pub fn new_at_loc(x: usize, y: usize) -> Self {
         let template = map_color_to_rgba(&BALL_ASSET);
         let point = Point::new(x, y);
-        let old_point = point.clone();
         let sprite = Sprite {
             width: 8,
             height: 8,
@@ -233,119 +360,217 @@ impl Ball {
         };
 
         Self {
-            old_point,
             point,
             sprite,
             velocity: BallVelocity::new(0.0, 0.0),
             fpos: Vec2::new(point.x as f64, point.y as f64),
+            prev_fpos: Vec2::new(point.x as f64, point.y as f64),
             power: BallPower::new(),
             angle: BallAngle::new(),
+            portal_cooldown: 0,
+            rest_ticks: 0,
+            support: None,
+            boosted: false,
         }
     }
 
     pub fn new() -> Self {
-        return Ball::new_at_loc(100, 100);
+        Ball::new_at_loc(100, 100)
     }
 
-    pub fn aim_path(&self) -> AimLine {
-        let ball_center = self.center();
-        let theta_sin = self.angle.xv(); //f64::sin(self.theta - PI / 2.0);
-        let theta_cos = self.angle.yv(); //f64::cos(self.theta + PI / 2.0);
-        let x1 = ball_center.x as f64 + 0.5 + self.power.power / 5.0 * 1.0 * 5.0 * theta_sin;
-        let y1 = ball_center.y as f64 + 0.5 + self.power.power / 5.0 * 1.0 * 5.0 * theta_cos;
-        let x2 = ball_center.x as f64 + 0.5 + self.power.power / 5.0 * 12.0 * 5.0 * theta_sin;
-        let y2 = ball_center.y as f64 + 0.5 + self.power.power / 5.0 * 12.0 * 5.0 * theta_cos;
-        let line = AimLine::new(x1 as usize, y1 as usize, x2 as usize, y2 as usize);
-        return line;
+    /// Plays the shot forward on a copy of the ball, as far as `preview`
+    /// allows. The path ends early where the ball would stop, or reach a
+    /// tile that is not plain rolling: a hole, portal, switch or hazard.
+    ///
+    /// `obstacles` are played forward alongside it. A shot asked for now is
+    /// struck on the second update from now, so they get two updates' start.
+    pub fn aim_path(
+        &self,
+        playground: &GameMap,
+        obstacles: &[Obstacle],
+        preview: &AimPreview,
+    ) -> AimLine {
+        let mut ghost = self.clone();
+        ghost.hit();
+        let mut obstacles = obstacles.to_vec();
+        for obstacle in obstacles.iter_mut() {
+            obstacle.advance(2.0 * PHYSICS_DT);
+        }
+        let mut points = Vec::new();
+        let mut bounces = 0;
+        if preview.max_ticks > 0 {
+            points.push(ghost.exact_center());
+        }
+        for _ in 0..preview.max_ticks {
+            for obstacle in obstacles.iter_mut() {
+                obstacle.advance(PHYSICS_DT);
+            }
+            bounces += ghost.roll(playground, &obstacles).len();
+            let center = ghost.exact_center();
+            points.push(center);
+            let tile = ghost.center();
+            let tile_type = playground
+                .tile_grid
+                .tile_at(tile.x >> 4, tile.y >> 4)
+                .get_type();
+            let ends_path = matches!(
+                tile_type,
+                TileType::Hole | TileType::Portal | TileType::Switch { .. }
+            ) || tile_type.is_hazard()
+                || playground.wells.iter().any(|well| well.swallows(center));
+            if ends_path || bounces > preview.max_bounces || ghost.is_stopped() {
+                break;
+            }
+        }
+        AimLine::new(points)
     }
 
     pub fn hit(&mut self) {
         self.velocity
             .update(plan_ball_velocity(self.angle.clone(), self.power.clone()));
+        self.rest_ticks = 0;
+        self.support = None;
     }
 
-    pub fn roll(&mut self, playground: &GameMap) {
-        self.handle_collision(playground);
-        self.old_point = self.point;
-        self.fpos = self.fpos + self.velocity.vec2();
-        self.point = Point::new(self.fpos.x as usize, self.fpos.y as usize);
-        self.velocity.slow();
-        if self.velocity.velocity_below(0.1) {
+    /// Advances the ball by one physics update of `PHYSICS_DT` seconds,
+    /// bouncing off any walls and obstacles in the way. Returns the normals
+    /// of the surfaces it bounced off.
+    pub fn roll(&mut self, playground: &GameMap, obstacles: &[Obstacle]) -> Vec<Vec2<f64>> {
+        self.prev_fpos = self.fpos;
+        let center = self.center();
+        let surface = playground
+            .tile_grid
+            .tile_at(center.x >> 4, center.y >> 4)
+            .get_type()
+            .acceleration(self.velocity.vec2());
+        let pull = gravity_at(&playground.wells, self.exact_center()) + surface;
+        self.velocity
+            .update(self.velocity.vec2() + pull.scale(PHYSICS_DT));
+        // Obstacles are only checked for overlap, so near them the ball moves
+        // in steps of at most half its radius. A ball clear of a part then
+        // never gets its center inside it, and is pushed back out the side it
+        // came from however thin the part is.
+        let mut bounces = Vec::new();
+        let mut remaining = PHYSICS_DT;
+        while remaining > 0.0 {
+            let speed = self.velocity.vec2().length();
+            let dt = if obstacles.is_empty() || speed * remaining <= self.radius() / 2.0 {
+                remaining
+            } else {
+                self.radius() / 2.0 / speed
+            };
+            remaining -= dt;
+            let sweep = move_circle(
+                &playground.tile_grid,
+                self.exact_center(),
+                self.radius(),
+                self.velocity.vec2(),
+                dt,
+            );
+            self.fpos = sweep.center - self.half_size();
+            self.point = Point::new(self.fpos.x as usize, self.fpos.y as usize);
+            self.velocity.update(sweep.velocity);
+            bounces.extend(sweep.contacts.iter().map(|c| c.normal));
+            bounces.extend(self.collide_obstacles(&playground.tile_grid, obstacles));
+        }
+        let center = self.center();
+        let tile = playground.tile_grid.tile_at(center.x >> 4, center.y >> 4);
+        self.velocity.slow(tile.friction(), PHYSICS_DT);
+        match tile.get_type().boost() {
+            Some(boost) if !self.boosted => {
+                self.velocity.update(self.velocity.vec2() + boost);
+                self.boosted = true;
+            }
+            Some(_) => {}
+            None => self.boosted = false,
+        }
+        self.support = match bounces.last() {
+            Some(normal) => Some((*normal, REST_TICKS)),
+            None => self
+                .support
+                .and_then(|(normal, ticks)| (ticks > 1).then_some((normal, ticks - 1))),
+        };
+        let held = self.unsupported(pull).length() <= REST_ACCELERATION;
+        if held && self.velocity.velocity_below(REST_SPEED) {
+            self.rest_ticks += 1;
+        } else {
+            self.rest_ticks = 0;
+        }
+        if self.rest_ticks >= REST_TICKS {
             self.velocity.stop();
         }
-    }
-    pub fn handle_collision(&mut self, map: &GameMap) {
-        if self.x_collides(map) {
-            self.point.x = self.old_point.x;
-            self.velocity.invert_x();
+        bounces
+    }
+    /// Lets `obstacles` push a ball sitting still. Returns the normals of
+    /// any that knocked it, after which it is rolling again.
+    pub fn knock(&mut self, playground: &GameMap, obstacles: &[Obstacle]) -> Vec<Vec2<f64>> {
+        let knocks = self.collide_obstacles(&playground.tile_grid, obstacles);
+        if !knocks.is_empty() {
+            self.prev_fpos = self.fpos;
+            self.rest_ticks = 0;
+            self.support = None;
         }
-        if self.y_collides(map) {
-            self.point.y = self.old_point.y;
-            self.velocity.invert_y();
+        knocks
+    }
+    /// Pushes the ball out of any obstacle it overlaps, no further than the
+    /// nearest wall, and bounces it off the ones moving into it. Returns the
+    /// normals of those it bounced off.
+    fn collide_obstacles(&mut self, grid: &TileGrid, obstacles: &[Obstacle]) -> Vec<Vec2<f64>> {
+        let mut bounces = Vec::new();
+        for obstacle in obstacles.iter() {
+            let center = self.exact_center();
+            let Some(hit) = obstacle.collide(center, self.radius()) else {
+                continue;
+            };
+            let push = hit.normal.scale(hit.depth + SKIN);
+            let pushed = match sweep_circle(grid, center, self.radius(), push) {
+                Some(contact) => push.scale(contact.time),
+                None => push,
+            };
+            self.fpos = center + pushed - self.half_size();
+            self.point = Point::new(self.fpos.x as usize, self.fpos.y as usize);
+            // Bounce in the obstacle's frame, so its motion is passed on.
+            let relative = self.velocity.vec2() - hit.surface_velocity;
+            if relative.x * hit.normal.x + relative.y * hit.normal.y < 0.0 {
+                let bounced = reflect(relative, hit.normal, OBSTACLE_RESTITUTION);
+                self.velocity.update(bounced + hit.surface_velocity);
+                bounces.push(hit.normal);
+            }
         }
-        // println!("Ball X Velocity: {} Ball Y Velocity: {}", self.vx, self.vy);
-    }
-    fn x_collides(&self, map: &GameMap) -> bool {
-        let y_pos = (self.center().y as i32) >> 4;
-        let x_upper = self.left() + 8;
-        let x_lower = self.right() - 8;
-        let x_pos_upper = (x_upper as i32) >> 4;
-        let x_pos_lower = (x_lower as i32) >> 4;
-        let x_upper_collides_wall = map
-            .tile_grid
-            .tile_at(x_pos_upper as usize, y_pos as usize)
-            .get_type()
-            == &TileType::Wall
-            && self.velocity.x() > 0.0;
-        let x_lower_collides_wall = map
-            .tile_grid
-            .tile_at(x_pos_lower as usize, y_pos as usize)
-            .get_type()
-            == &TileType::Wall
-            && self.velocity.x() < 0.0;
-
-        return x_upper_collides_wall || x_lower_collides_wall || !self.is_within_x_bounds(map);
-    }
-    fn y_collides(&self, map: &GameMap) -> bool {
-        let x_pos = (self.center().x as i32) >> 4;
-        let y_upper = self.top() + 8;
-        let y_lower = self.bottom() - 8;
-        let y_pos_upper = (y_upper as i32) >> 4;
-        let y_pos_lower = (y_lower as i32) >> 4;
-        let y_upper_collides_wall = map
-            .tile_grid
-            .tile_at(x_pos as usize, y_pos_upper as usize)
-            .get_type()
-            == &TileType::Wall
-            && self.velocity.y() > 0.0;
-        let y_lower_collides_wall = map
-            .tile_grid
-            .tile_at(x_pos as usize, y_pos_lower as usize)
-            .get_type()
-            == &TileType::Wall
-            && self.velocity.y() < 0.0;
-        return y_upper_collides_wall || y_lower_collides_wall || !self.is_within_y_bounds(map);
-    }
-
-    fn is_within_x_bounds(&self, playground: &GameMap) -> bool {
-        if self.point.x < playground.left() || self.point.x + self.sprite.width > playground.right()
-        {
-            return false;
-        } else {
-            return true;
+        bounces
+    }
+    /// The part of `acceleration` not taken up by a wall the ball was
+    /// recently pressed against.
+    fn unsupported(&self, acceleration: Vec2<f64>) -> Vec2<f64> {
+        match self.support {
+            Some((normal, _)) => {
+                let into = acceleration.x * normal.x + acceleration.y * normal.y;
+                if into < 0.0 {
+                    acceleration - normal.scale(into)
+                } else {
+                    acceleration
+                }
+            }
+            None => acceleration,
         }
     }
-    fn is_within_y_bounds(&self, playground: &GameMap) -> bool {
-        if self.point.y < playground.top()
-            || self.point.y + self.sprite.height > playground.bottom()
-        {
-            return false;
-        } else {
-            return true;
-        }
+    /// Whether the ball has come to rest.
+    pub fn is_stopped(&self) -> bool {
+        self.velocity.x() == 0.0 && self.velocity.y() == 0.0
+    }
+    /// Radius of the round ball, for collisions.
+    pub fn radius(&self) -> f64 {
+        (self.sprite.width / 2) as f64
+    }
+    /// Half the width and height of the ball.
+    pub fn half_size(&self) -> Vec2<f64> {
+        Vec2::new(
+            (self.sprite.width / 2) as f64,
+            (self.sprite.height / 2) as f64,
+        )
     }
     pub fn point(&self) -> Point {
-        return self.point;
+        self.point
     }
 
     pub fn loc_x(&mut self, x: usize) {
@@ -367,6 +592,14 @@ impl Ball {
         )
     }
 
+    /// Returns the sub-pixel center position of the ball.
+    pub fn exact_center(&self) -> Vec2<f64> {
+        Vec2::new(
+            self.fpos.x + (self.sprite.width / 2) as f64,
+            self.fpos.y + (self.sprite.height / 2) as f64,
+        )
+    }
+
     /// Returns the left edge of the `Rect`
     pub fn left(&self) -> usize {
         self.point.x
@@ -394,13 +627,51 @@ impl Ball {
     }
 
     pub fn reset_at(&mut self, point: Point) {
-        self.old_point = point.clone();
         self.point = point;
         self.velocity = BallVelocity::new(0.0, 0.0);
         self.fpos = Vec2::new(point.x as f64, point.y as f64);
+        self.prev_fpos = self.fpos;
+        self.portal_cooldown = 0;
+        self.rest_ticks = 0;
+        self.support = None;
+        self.boosted = false;
+    }
+
+    /// Puts the ball down at rest, centered on `center`.
+    pub fn place(&mut self, center: Vec2<f64>) {
+        self.teleport(center, Vec2::new(0.0, 0.0));
+        self.portal_cooldown = 0;
+        self.rest_ticks = 0;
+        self.support = None;
+        self.boosted = false;
+    }
+
+    /// Moves the ball so it is centered on `center` and keeps it rolling
+    /// with `velocity`. Starts the portal cooldown.
+    pub fn teleport(&mut self, center: Vec2<f64>, velocity: Vec2<f64>) {
+        let x = center.x - (self.sprite.width / 2) as f64;
+        let y = center.y - (self.sprite.height / 2) as f64;
+        self.fpos = Vec2::new(x, y);
+        self.prev_fpos = self.fpos;
+        self.point = Point::new(x as usize, y as usize);
+        self.velocity.update(velocity);
+        self.portal_cooldown = PORTAL_COOLDOWN_TICKS;
+    }
+
+    pub fn draw(&self, frame: &mut [u8], camera: &Camera) {
+        let (x, y) = camera.offset();
+        blit(
+            frame,
+            &camera.view(),
+            (self.point.x as i64 - x, self.point.y as i64 - y),
+            &self.sprite,
+        );
     }
 
-    pub fn draw(&self, frame: &mut [u8]) {
-        blit(frame, &self.point, &self.sprite);
+    /// Draws the ball `alpha` of the way from its previous physics position
+    /// to its current one.
+    pub fn draw_interpolated(&self, frame: &mut [u8], camera: &Camera, alpha: f64) {
+        let pos = self.prev_fpos + (self.fpos - self.prev_fpos).scale(alpha.clamp(0.0, 1.0));
+        blit(frame, &camera.view(), camera.to_screen(pos), &self.sprite);
     }
 }
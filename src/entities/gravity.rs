@@ -0,0 +1,77 @@
+use crate::camera::*;
+use crate::common::*;
+use crate::geo::*;
+use crate::physics::TILE_PIXELS;
+use crate::render::helpers::*;
+
+/// What kind of body a gravity well is.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum WellKind {
+    /// Pulls the ball around. The ball rolls over it.
+    Planet,
+    /// Pulls the ball in and loses it once it is inside the radius.
+    BlackHole,
+}
+
+/// A point that pulls the ball towards it.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GravityWell {
+    pub kind: WellKind,
+    /// Pixel position of the center.
+    pub center: Vec2<f64>,
+    /// Pull in pixels per second squared felt one tile from the center,
+    /// falling off with the square of the distance.
+    pub mass: f64,
+    /// Size of the body in pixels. The pull is softened inside it so it
+    /// never becomes infinite.
+    pub radius: f64,
+}
+
+impl GravityWell {
+    pub fn new(kind: WellKind, center: Vec2<f64>, mass: f64, radius: f64) -> Self {
+        Self {
+            kind,
+            center,
+            mass,
+            radius,
+        }
+    }
+
+    /// Acceleration in pixels per second squared of a ball at `at`.
+    pub fn pull(&self, at: Vec2<f64>) -> Vec2<f64> {
+        let toward = self.center - at;
+        let distance_sq = toward.x * toward.x + toward.y * toward.y + self.radius * self.radius;
+        if distance_sq == 0.0 {
+            return Vec2::new(0.0, 0.0);
+        }
+        let strength = self.mass * TILE_PIXELS * TILE_PIXELS / (distance_sq * distance_sq.sqrt());
+        toward.scale(strength)
+    }
+
+    /// Whether a ball centered on `at` is lost to this well.
+    pub fn swallows(&self, at: Vec2<f64>) -> bool {
+        self.kind == WellKind::BlackHole && (self.center - at).length() < self.radius
+    }
+
+    pub fn draw(&self, frame: &mut [u8], camera: &Camera) {
+        let center = camera.to_screen(self.center);
+        let view = camera.view();
+        let radius = self.radius as i64;
+        match self.kind {
+            WellKind::Planet => {
+                circle(frame, &view, center, radius, PLANET_COLOR);
+            }
+            WellKind::BlackHole => {
+                circle(frame, &view, center, radius, BLACK_HOLE_RIM_COLOR);
+                circle(frame, &view, center, radius - 2, BLACK_HOLE_COLOR);
+            }
+        }
+    }
+}
+
+/// Total acceleration from every well on a ball at `at`.
+pub fn gravity_at(wells: &[GravityWell], at: Vec2<f64>) -> Vec2<f64> {
+    wells
+        .iter()
+        .fold(Vec2::new(0.0, 0.0), |total, well| total + well.pull(at))
+}
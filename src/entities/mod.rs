@@ -1,3 +1,6 @@
 pub mod assests;
 pub mod ball;
+pub mod gravity;
+pub mod obstacles;
+pub mod portals;
 pub mod tiles;
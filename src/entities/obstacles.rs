@@ -0,0 +1,311 @@
+use crate::camera::*;
+use crate::common::*;
+use crate::geo::*;
+use crate::render::helpers::*;
+use crate::render::shapes::*;
+
+/// Thickness in pixels of bars and windmill blades.
+pub const BAR_THICKNESS: f64 = 4.0;
+/// Fraction of speed the ball keeps bouncing off an obstacle, measured
+/// against the obstacle's own motion.
+pub const OBSTACLE_RESTITUTION: f64 = 0.8;
+
+/// The shape of a moving obstacle. Sizes are in pixels.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ObstacleKind {
+    /// A solid box.
+    Block { width: f64, height: f64 },
+    /// A thin bar turning about its middle.
+    Bar { length: f64 },
+    /// `blades` thin bars of `length` spread evenly around a hub.
+    Windmill { blades: u32, length: f64 },
+}
+
+/// Where an obstacle is at one moment of its track.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Keyframe {
+    /// Seconds from the start of the track.
+    pub time: f64,
+    /// Pixels moved from the obstacle's anchor.
+    pub offset: Vec2<f64>,
+    /// Turn in radians, clockwise on screen.
+    pub angle: f64,
+}
+
+/// A keyframed path, played on a loop. The obstacle moves in a straight line
+/// and turns at a steady rate between keyframes, and jumps back to the first
+/// keyframe after the last, so a track that should not jump ends where it
+/// starts.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Track {
+    keyframes: Vec<Keyframe>,
+}
+
+/// How an obstacle is placed and moving at one moment.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Pose {
+    /// Pixel position of the point the obstacle turns about.
+    pub center: Vec2<f64>,
+    pub angle: f64,
+    /// Pixels per second.
+    pub velocity: Vec2<f64>,
+    /// Radians per second.
+    pub spin: f64,
+}
+
+/// A box making up part of an obstacle.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Part {
+    pub center: Vec2<f64>,
+    /// Half the width and height before turning.
+    pub half: Vec2<f64>,
+    pub angle: f64,
+}
+
+/// Where a ball overlaps an obstacle.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ObstacleHit {
+    /// Unit normal pointing from the obstacle towards the ball.
+    pub normal: Vec2<f64>,
+    /// How far the ball has to move along `normal` to stop overlapping.
+    pub depth: f64,
+    /// Velocity of the obstacle's surface where the ball touches it.
+    pub surface_velocity: Vec2<f64>,
+}
+
+/// A block, bar or windmill following a `Track` around its anchor.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Obstacle {
+    pub kind: ObstacleKind,
+    /// Pixel position the track's offsets are measured from.
+    pub anchor: Vec2<f64>,
+    pub track: Track,
+    /// Seconds played of the track.
+    time: f64,
+}
+
+impl Track {
+    /// A track through `keyframes`, which have to be in time order.
+    pub fn new(keyframes: Vec<Keyframe>) -> Self {
+        debug_assert!(keyframes.windows(2).all(|k| k[0].time < k[1].time));
+        Self { keyframes }
+    }
+
+    pub fn keyframes(&self) -> &[Keyframe] {
+        &self.keyframes
+    }
+
+    /// Adds a keyframe after the others.
+    pub fn push(&mut self, keyframe: Keyframe) {
+        debug_assert!(self.keyframes.last().is_none_or(|k| k.time < keyframe.time));
+        self.keyframes.push(keyframe);
+    }
+
+    /// Seconds before the track starts again.
+    pub fn period(&self) -> f64 {
+        self.keyframes.last().map_or(0.0, |k| k.time)
+    }
+
+    /// Offset, angle, velocity and spin `time` seconds into the track.
+    fn motion_at(&self, time: f64) -> (Vec2<f64>, f64, Vec2<f64>, f64) {
+        let still = Vec2::new(0.0, 0.0);
+        let Some(first) = self.keyframes.first() else {
+            return (still, 0.0, still, 0.0);
+        };
+        let period = self.period();
+        let time = if period > 0.0 {
+            time.rem_euclid(period)
+        } else {
+            0.0
+        };
+        if time < first.time || self.keyframes.len() == 1 {
+            return (first.offset, first.angle, still, 0.0);
+        }
+        let next = self
+            .keyframes
+            .iter()
+            .position(|k| k.time > time)
+            .unwrap_or(self.keyframes.len() - 1);
+        let (from, to) = (&self.keyframes[next - 1], &self.keyframes[next]);
+        let span = to.time - from.time;
+        let part = (time - from.time) / span;
+        let velocity = (to.offset - from.offset).scale(1.0 / span);
+        let spin = (to.angle - from.angle) / span;
+        (
+            from.offset + (to.offset - from.offset).scale(part),
+            from.angle + (to.angle - from.angle) * part,
+            velocity,
+            spin,
+        )
+    }
+}
+
+impl Obstacle {
+    pub fn new(kind: ObstacleKind, anchor: Vec2<f64>, track: Track) -> Self {
+        Self {
+            kind,
+            anchor,
+            track,
+            time: 0.0,
+        }
+    }
+
+    /// Seconds played of the track.
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    /// Moves the obstacle `dt` seconds along its track.
+    pub fn advance(&mut self, dt: f64) {
+        self.time += dt;
+    }
+
+    pub fn pose(&self) -> Pose {
+        let (offset, angle, velocity, spin) = self.track.motion_at(self.time);
+        Pose {
+            center: self.anchor + offset,
+            angle,
+            velocity,
+            spin,
+        }
+    }
+
+    /// The boxes the obstacle is made of, where they are now.
+    pub fn parts(&self) -> Vec<Part> {
+        let pose = self.pose();
+        match self.kind {
+            ObstacleKind::Block { width, height } => vec![Part {
+                center: pose.center,
+                half: Vec2::new(width / 2.0, height / 2.0),
+                angle: pose.angle,
+            }],
+            ObstacleKind::Bar { length } => vec![Part {
+                center: pose.center,
+                half: Vec2::new(length / 2.0, BAR_THICKNESS / 2.0),
+                angle: pose.angle,
+            }],
+            ObstacleKind::Windmill { blades, length } => (0..blades)
+                .map(|blade| {
+                    let angle = pose.angle + std::f64::consts::TAU * blade as f64 / blades as f64;
+                    Part {
+                        center: pose.center + turn(Vec2::new(length / 2.0, 0.0), angle),
+                        half: Vec2::new(length / 2.0, BAR_THICKNESS / 2.0),
+                        angle,
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    /// How a circle of `radius` centered on `center` overlaps the obstacle,
+    /// using the part it is deepest into.
+    pub fn collide(&self, center: Vec2<f64>, radius: f64) -> Option<ObstacleHit> {
+        let pose = self.pose();
+        let mut deepest: Option<ObstacleHit> = None;
+        for part in self.parts() {
+            let Some((normal, depth, touching)) = part.overlap(center, radius) else {
+                continue;
+            };
+            if deepest.is_some_and(|hit| hit.depth >= depth) {
+                continue;
+            }
+            // A point turning about the center moves at right angles to it.
+            let arm = touching - pose.center;
+            let surface_velocity = pose.velocity + Vec2::new(-arm.y, arm.x).scale(pose.spin);
+            deepest = Some(ObstacleHit {
+                normal,
+                depth,
+                surface_velocity,
+            });
+        }
+        deepest
+    }
+
+    pub fn draw(&self, frame: &mut [u8], camera: &Camera) {
+        for part in self.parts() {
+            part.draw(frame, camera);
+        }
+        if let ObstacleKind::Windmill { .. } = self.kind {
+            let hub = self.pose().center;
+            let (x, y) = camera.offset();
+            circle(
+                frame,
+                &camera.view(),
+                (
+                    (hub.x.round() as i64).saturating_sub(x),
+                    (hub.y.round() as i64).saturating_sub(y),
+                ),
+                BAR_THICKNESS as i64,
+                OBSTACLE_HUB_COLOR,
+            );
+        }
+    }
+}
+
+impl Part {
+    /// The normal, depth and point of contact of a circle overlapping the
+    /// box, if it does.
+    fn overlap(&self, center: Vec2<f64>, radius: f64) -> Option<(Vec2<f64>, f64, Vec2<f64>)> {
+        let local = turn(center - self.center, -self.angle);
+        let closest = Vec2::new(
+            local.x.clamp(-self.half.x, self.half.x),
+            local.y.clamp(-self.half.y, self.half.y),
+        );
+        let outside = local - closest;
+        let distance = outside.length();
+        let (normal, depth) = if distance > 0.0 {
+            if distance >= radius {
+                return None;
+            }
+            (outside.scale(1.0 / distance), radius - distance)
+        } else {
+            // The center is inside, leave by the nearest side.
+            let (x_depth, y_depth) = (self.half.x - local.x.abs(), self.half.y - local.y.abs());
+            if x_depth < y_depth {
+                (Vec2::new(local.x.signum(), 0.0), x_depth + radius)
+            } else {
+                (Vec2::new(0.0, local.y.signum()), y_depth + radius)
+            }
+        };
+        Some((
+            turn(normal, self.angle),
+            depth,
+            self.center + turn(closest, self.angle),
+        ))
+    }
+
+    fn draw(&self, frame: &mut [u8], camera: &Camera) {
+        let reach = self.half.length().ceil() as i64;
+        let (ox, oy) = camera.offset();
+        // Saturating, as a center far off the map would overflow.
+        let (cx, cy) = (
+            (self.center.x.round() as i64).saturating_sub(ox),
+            (self.center.y.round() as i64).saturating_sub(oy),
+        );
+        let area = Rect::new(
+            Vec2::new(cx.saturating_sub(reach), cy.saturating_sub(reach)),
+            Vec2::new(
+                cx.saturating_add(reach).saturating_add(1),
+                cy.saturating_add(reach).saturating_add(1),
+            ),
+        )
+        .intersection(&camera.view().intersection(&Rect::screen()));
+        for y in area.p1.y..area.p2.y {
+            for x in area.p1.x..area.p2.x {
+                let pixel = Vec2::new((x + ox) as f64 + 0.5, (y + oy) as f64 + 0.5);
+                let local = turn(pixel - self.center, -self.angle);
+                if local.x.abs() > self.half.x || local.y.abs() > self.half.y {
+                    continue;
+                }
+                let i = x as usize * 4 + y as usize * WIDTH * 4;
+                frame[i..i + 4].copy_from_slice(&OBSTACLE_COLOR);
+            }
+        }
+    }
+}
+
+/// Turns `v` by `angle` radians, clockwise on screen.
+fn turn(v: Vec2<f64>, angle: f64) -> Vec2<f64> {
+    let (sin, cos) = angle.sin_cos();
+    Vec2::new(v.x * cos - v.y * sin, v.x * sin + v.y * cos)
+}
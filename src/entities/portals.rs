@@ -0,0 +1,119 @@
+use crate::common::*;
+use crate::geo::*;
+
+/// Number of physics ticks the ball has to spend off portal tiles before it
+/// can be teleported again, a quarter of a second. Stops it from bouncing
+/// straight back.
+pub const PORTAL_COOLDOWN_TICKS: u32 = PHYSICS_HZ / 4;
+
+/// The direction a ball leaves an exit portal in.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Facing {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Facing {
+    /// Unit vector pointing out of the portal.
+    pub fn unit(&self) -> Vec2<f64> {
+        match self {
+            Facing::Up => Vec2::new(0.0, -1.0),
+            Facing::Down => Vec2::new(0.0, 1.0),
+            Facing::Left => Vec2::new(-1.0, 0.0),
+            Facing::Right => Vec2::new(1.0, 0.0),
+        }
+    }
+}
+
+/// A single portal tile, addressed by its column and row in the `TileGrid`.
+#[derive(Copy, Clone, Debug)]
+pub struct Portal {
+    pub tile: Point,
+    pub facing: Option<Facing>,
+}
+
+impl Portal {
+    pub fn new(column: usize, row: usize, facing: Option<Facing>) -> Self {
+        Self {
+            tile: Point::new(column, row),
+            facing,
+        }
+    }
+
+    /// Pixel position of the center of the portal tile.
+    pub fn center(&self) -> Vec2<f64> {
+        Vec2::new((self.tile.x * 16 + 8) as f64, (self.tile.y * 16 + 8) as f64)
+    }
+
+    /// Carries `velocity` through the portal. The speed is kept, and if the
+    /// portal has a facing the direction is turned to match it.
+    pub fn exit_velocity(&self, velocity: Vec2<f64>) -> Vec2<f64> {
+        match self.facing {
+            Some(facing) => facing.unit().scale(velocity.length()),
+            None => velocity,
+        }
+    }
+}
+
+/// Portals that are linked together. Entering one sends the ball out of the
+/// next one in the group, wrapping back around to the first.
+#[derive(Clone, Debug, Default)]
+pub struct PortalGroup {
+    portals: Vec<Portal>,
+}
+
+impl PortalGroup {
+    pub fn new(portals: Vec<Portal>) -> Self {
+        Self { portals }
+    }
+
+    /// Links two portals to each other.
+    pub fn pair(a: Portal, b: Portal) -> Self {
+        Self::new(vec![a, b])
+    }
+
+    pub fn portals(&self) -> &[Portal] {
+        &self.portals
+    }
+}
+
+/// Every portal group on a map.
+#[derive(Clone, Debug, Default)]
+pub struct PortalNetwork {
+    groups: Vec<PortalGroup>,
+}
+
+impl PortalNetwork {
+    pub fn new() -> Self {
+        Self { groups: Vec::new() }
+    }
+
+    pub fn link(&mut self, group: PortalGroup) {
+        self.groups.push(group);
+    }
+
+    pub fn groups(&self) -> &[PortalGroup] {
+        &self.groups
+    }
+
+    /// Finds the portal a ball entering the portal at (`column`, `row`)
+    /// comes out of. Unlinked portals return `None`.
+    pub fn exit_for(&self, column: usize, row: usize) -> Option<&Portal> {
+        for group in self.groups.iter() {
+            let count = group.portals.len();
+            if count < 2 {
+                continue;
+            }
+            let entry = group
+                .portals
+                .iter()
+                .position(|p| p.tile.x == column && p.tile.y == row);
+            if let Some(index) = entry {
+                return Some(&group.portals[(index + 1) % count]);
+            }
+        }
+        None
+    }
+}
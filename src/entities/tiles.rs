@@ -1,13 +1,455 @@
+use crate::camera::*;
 use crate::common::*;
+use crate::entities::portals::*;
 use crate::geo::*;
+use crate::map::level::*;
 use crate::render::helpers::*;
 use crate::render::sprites::*;
+/// One corner of a tile.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl Corner {
+    /// Position of the corner in a tile of size `size`, from its top left.
+    pub fn offset(&self, size: f64) -> Vec2<f64> {
+        match self {
+            Corner::TopLeft => Vec2::new(0.0, 0.0),
+            Corner::TopRight => Vec2::new(size, 0.0),
+            Corner::BottomLeft => Vec2::new(0.0, size),
+            Corner::BottomRight => Vec2::new(size, size),
+        }
+    }
+
+    pub fn opposite(&self) -> Corner {
+        match self {
+            Corner::TopLeft => Corner::BottomRight,
+            Corner::TopRight => Corner::BottomLeft,
+            Corner::BottomLeft => Corner::TopRight,
+            Corner::BottomRight => Corner::TopLeft,
+        }
+    }
+
+    /// The two corners sharing a side with this one.
+    pub fn neighbors(&self) -> [Corner; 2] {
+        match self {
+            Corner::TopLeft => [Corner::TopRight, Corner::BottomLeft],
+            Corner::TopRight => [Corner::TopLeft, Corner::BottomRight],
+            Corner::BottomLeft => [Corner::TopLeft, Corner::BottomRight],
+            Corner::BottomRight => [Corner::TopRight, Corner::BottomLeft],
+        }
+    }
+}
+
+/// The part of a tile the ball bounces off.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TileShape {
+    Empty,
+    Full,
+    /// The half of the tile on the `Corner` side of a 45° diagonal.
+    Diagonal(Corner),
+    /// The `Corner` side of a quarter circle as big as the tile, centered on
+    /// the opposite corner. Rounds off the inside corner of a room.
+    Curved(Corner),
+    /// A quarter disc as big as the tile, centered on the `Corner`. Rounds
+    /// off the outside corner of a wall.
+    Rounded(Corner),
+}
+
+impl TileShape {
+    /// Whether the point `x`, `y` pixels from the tile's top left is inside
+    /// the solid part of a tile of size `size`.
+    pub fn contains(&self, x: f64, y: f64, size: f64) -> bool {
+        if x < 0.0 || y < 0.0 || x > size || y > size {
+            return false;
+        }
+        match self {
+            TileShape::Empty => false,
+            TileShape::Full => true,
+            TileShape::Diagonal(corner) => {
+                let to = corner.opposite().offset(size);
+                // Manhattan distance from the open corner past the diagonal.
+                (x - to.x).abs() + (y - to.y).abs() > size
+            }
+            TileShape::Curved(corner) => {
+                let center = corner.opposite().offset(size);
+                (Vec2::new(x, y) - center).length() > size
+            }
+            TileShape::Rounded(corner) => {
+                let center = corner.offset(size);
+                (Vec2::new(x, y) - center).length() < size
+            }
+        }
+    }
+}
+
+/// The way a slope runs down.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Downhill {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Downhill {
+    /// Unit vector pointing down the slope.
+    pub fn unit(&self) -> Vec2<f64> {
+        let diagonal = std::f64::consts::FRAC_1_SQRT_2;
+        match self {
+            Downhill::Up => Vec2::new(0.0, -1.0),
+            Downhill::UpRight => Vec2::new(diagonal, -diagonal),
+            Downhill::Right => Vec2::new(1.0, 0.0),
+            Downhill::DownRight => Vec2::new(diagonal, diagonal),
+            Downhill::Down => Vec2::new(0.0, 1.0),
+            Downhill::DownLeft => Vec2::new(-diagonal, diagonal),
+            Downhill::Left => Vec2::new(-1.0, 0.0),
+            Downhill::UpLeft => Vec2::new(-diagonal, -diagonal),
+        }
+    }
+}
+
+/// Steepest a slope can be.
+pub const MAX_SLOPE_STRENGTH: u8 = 3;
+/// Acceleration down a slope, in pixels per second squared, for each step of
+/// strength.
+pub const SLOPE_ACCELERATION: f64 = 45.0;
+/// Fraction of its speed into a bumper a ball leaves with.
+pub const BUMPER_RESTITUTION: f64 = 1.5;
+/// Speed in pixels per second a booster adds to a ball rolling onto it.
+pub const BOOST_SPEED: f64 = 160.0;
+/// Fastest a conveyor can run.
+pub const MAX_CONVEYOR_SPEED: u8 = 3;
+/// Pixels per second a conveyor carries the ball for each step of speed.
+pub const CONVEYOR_SPEED: f64 = 30.0;
+/// How quickly a conveyor brings the ball up to its speed, the fraction of
+/// the difference made up each second.
+pub const CONVEYOR_GRIP: f64 = 4.0;
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum TileType {
     Ground,
     Wall,
+    /// A wall cut along a 45° diagonal, solid on the `Corner` side.
+    DiagonalWall(Corner),
+    /// A wall with a quarter circle cut out of it, solid on the `Corner` side.
+    CurvedWall(Corner),
+    /// A quarter disc of wall, solid on the `Corner` side.
+    RoundedWall(Corner),
     Portal,
     Hole,
+    /// Slows the ball down much faster than ground.
+    Sand,
+    /// Loses the ball.
+    Water,
+    /// Out of bounds, loses the ball.
+    Void,
+    /// Ground that pushes the ball `downhill`, harder the higher `strength`
+    /// is, from 1 to `MAX_SLOPE_STRENGTH`.
+    Slope {
+        downhill: Downhill,
+        strength: u8,
+    },
+    /// A wall that throws the ball back faster than it came.
+    Bumper,
+    /// Ground that adds speed towards `facing` to a ball rolling onto it.
+    Booster {
+        facing: Facing,
+    },
+    /// Ground moving towards `facing` that carries the ball along, faster the
+    /// higher `speed` is, from 1 to `MAX_CONVEYOR_SPEED`.
+    Conveyor {
+        facing: Facing,
+        speed: u8,
+    },
+    /// A wall that switches open and closed. Gates sharing an `id` move
+    /// together.
+    Gate {
+        id: u8,
+        open: bool,
+    },
+    /// Toggles the gates the level links to switch `id` when the ball rolls
+    /// onto it.
+    Switch {
+        id: u8,
+    },
+    /// A wall the ball only passes through moving towards `facing`.
+    OneWay {
+        facing: Facing,
+    },
+}
+
+impl TileType {
+    /// Parses the character used for this tile in level files.
+    pub fn from_symbol(symbol: char) -> Option<TileType> {
+        match symbol {
+            '.' => Some(TileType::Ground),
+            '#' => Some(TileType::Wall),
+            '7' => Some(TileType::DiagonalWall(Corner::TopLeft)),
+            '9' => Some(TileType::DiagonalWall(Corner::TopRight)),
+            '1' => Some(TileType::DiagonalWall(Corner::BottomLeft)),
+            '3' => Some(TileType::DiagonalWall(Corner::BottomRight)),
+            'u' => Some(TileType::CurvedWall(Corner::TopLeft)),
+            'i' => Some(TileType::CurvedWall(Corner::TopRight)),
+            'j' => Some(TileType::CurvedWall(Corner::BottomLeft)),
+            'k' => Some(TileType::CurvedWall(Corner::BottomRight)),
+            'U' => Some(TileType::RoundedWall(Corner::TopLeft)),
+            'I' => Some(TileType::RoundedWall(Corner::TopRight)),
+            'J' => Some(TileType::RoundedWall(Corner::BottomLeft)),
+            'K' => Some(TileType::RoundedWall(Corner::BottomRight)),
+            '@' => Some(TileType::Portal),
+            'O' => Some(TileType::Hole),
+            ':' => Some(TileType::Sand),
+            '~' => Some(TileType::Water),
+            'x' => Some(TileType::Void),
+            '*' => Some(TileType::Bumper),
+            _ => None,
+        }
+    }
+
+    /// Whether the ball bounces off any part of this tile.
+    pub fn is_solid(&self) -> bool {
+        self.shape() != TileShape::Empty
+    }
+
+    /// The part of this tile the ball bounces off.
+    pub fn shape(&self) -> TileShape {
+        match self {
+            TileType::Wall | TileType::Bumper | TileType::OneWay { .. } => TileShape::Full,
+            TileType::Gate { open: false, .. } => TileShape::Full,
+            TileType::DiagonalWall(corner) => TileShape::Diagonal(*corner),
+            TileType::CurvedWall(corner) => TileShape::Curved(*corner),
+            TileType::RoundedWall(corner) => TileShape::Rounded(*corner),
+            _ => TileShape::Empty,
+        }
+    }
+
+    /// Acceleration, in pixels per second squared, of a ball rolling on this
+    /// tile with `velocity`.
+    pub fn acceleration(&self, velocity: Vec2<f64>) -> Vec2<f64> {
+        match self {
+            TileType::Slope { downhill, strength } => {
+                downhill.unit().scale(SLOPE_ACCELERATION * *strength as f64)
+            }
+            TileType::Conveyor { facing, speed } => {
+                // Only the speed along the belt is pulled towards its own.
+                let unit = facing.unit();
+                let along = velocity.x * unit.x + velocity.y * unit.y;
+                unit.scale(CONVEYOR_GRIP * (CONVEYOR_SPEED * *speed as f64 - along))
+            }
+            _ => Vec2::new(0.0, 0.0),
+        }
+    }
+
+    /// Velocity added to a ball when it rolls onto this tile.
+    pub fn boost(&self) -> Option<Vec2<f64>> {
+        match self {
+            TileType::Booster { facing } => Some(facing.unit().scale(BOOST_SPEED)),
+            _ => None,
+        }
+    }
+
+    /// Whether a ball rolling onto this tile is lost.
+    pub fn is_hazard(&self) -> bool {
+        matches!(self, TileType::Water | TileType::Void)
+    }
+
+    /// Fraction of its speed a ball rolling on this tile keeps each second,
+    /// unless the level sets its own.
+    pub fn default_friction(&self) -> f64 {
+        match self {
+            TileType::Ground => 0.75,
+            TileType::Wall => 0.75,
+            TileType::DiagonalWall(_) => 0.75,
+            TileType::CurvedWall(_) => 0.75,
+            TileType::RoundedWall(_) => 0.75,
+            TileType::Portal => 0.75,
+            TileType::Hole => 0.75,
+            TileType::Sand => 0.1,
+            TileType::Water => 0.75,
+            TileType::Void => 0.75,
+            TileType::Slope { .. } => 0.75,
+            TileType::Bumper => 0.75,
+            TileType::Booster { .. } => 0.75,
+            TileType::Conveyor { .. } => 0.75,
+            TileType::Gate { .. } => 0.75,
+            TileType::Switch { .. } => 0.75,
+            TileType::OneWay { .. } => 0.75,
+        }
+    }
+
+    /// Fraction of its speed into the tile a ball keeps when bouncing off
+    /// it, unless the level sets its own. Only used for solid tiles.
+    pub fn default_restitution(&self) -> f64 {
+        match self {
+            TileType::Ground => 1.0,
+            TileType::Wall => 0.8,
+            TileType::DiagonalWall(_) => 0.8,
+            TileType::CurvedWall(_) => 0.8,
+            TileType::RoundedWall(_) => 0.8,
+            TileType::Portal => 1.0,
+            TileType::Hole => 1.0,
+            TileType::Sand => 1.0,
+            TileType::Water => 1.0,
+            TileType::Void => 1.0,
+            TileType::Slope { .. } => 1.0,
+            TileType::Bumper => BUMPER_RESTITUTION,
+            TileType::Booster { .. } => 1.0,
+            TileType::Conveyor { .. } => 1.0,
+            TileType::Gate { .. } => 0.8,
+            TileType::Switch { .. } => 1.0,
+            TileType::OneWay { .. } => 0.8,
+        }
+    }
+
+    /// The character used for this tile in level files. Slopes, boosters,
+    /// conveyors, gates, switches and one-way walls have none, each level
+    /// picks its own.
+    pub fn symbol(&self) -> Option<char> {
+        let symbol = match self {
+            TileType::Ground => '.',
+            TileType::Wall => '#',
+            TileType::DiagonalWall(Corner::TopLeft) => '7',
+            TileType::DiagonalWall(Corner::TopRight) => '9',
+            TileType::DiagonalWall(Corner::BottomLeft) => '1',
+            TileType::DiagonalWall(Corner::BottomRight) => '3',
+            TileType::CurvedWall(Corner::TopLeft) => 'u',
+            TileType::CurvedWall(Corner::TopRight) => 'i',
+            TileType::CurvedWall(Corner::BottomLeft) => 'j',
+            TileType::CurvedWall(Corner::BottomRight) => 'k',
+            TileType::RoundedWall(Corner::TopLeft) => 'U',
+            TileType::RoundedWall(Corner::TopRight) => 'I',
+            TileType::RoundedWall(Corner::BottomLeft) => 'J',
+            TileType::RoundedWall(Corner::BottomRight) => 'K',
+            TileType::Portal => '@',
+            TileType::Hole => 'O',
+            TileType::Sand => ':',
+            TileType::Water => '~',
+            TileType::Void => 'x',
+            TileType::Bumper => '*',
+            TileType::Slope { .. }
+            | TileType::Booster { .. }
+            | TileType::Conveyor { .. }
+            | TileType::Gate { .. }
+            | TileType::Switch { .. }
+            | TileType::OneWay { .. } => return None,
+        };
+        Some(symbol)
+    }
+}
+
+/// A sprite with wall pixels on the solid part of `shape` and ground pixels
+/// on the rest.
+fn shaped_asset(shape: TileShape) -> [u8; TILE_SIZE] {
+    let mut asset = GROUND_TILE_ASSET;
+    for (i, pixel) in asset.iter_mut().enumerate() {
+        let (x, y) = ((i % 16) as f64 + 0.5, (i / 16) as f64 + 0.5);
+        if shape.contains(x, y, 16.0) {
+            *pixel = WALL_TILE_ASSET[i];
+        }
+    }
+    asset
+}
+
+/// A ground sprite shaded in bands that go from light to dark downhill.
+/// Steeper slopes have narrower bands.
+fn slope_asset(downhill: Downhill, strength: u8) -> [u8; TILE_SIZE] {
+    let unit = downhill.unit();
+    let band = 16.0 / strength.max(1) as f64;
+    let mut asset = GROUND_TILE_ASSET;
+    for (i, pixel) in asset.iter_mut().enumerate() {
+        let (x, y) = ((i % 16) as f64 + 0.5, (i / 16) as f64 + 0.5);
+        let along = (x * unit.x + y * unit.y).rem_euclid(band) / band;
+        *pixel = if along < 1.0 / 3.0 {
+            SLOPE_LIGHT
+        } else if along < 2.0 / 3.0 {
+            GROUND_TILE_ASSET[i]
+        } else {
+            SLOPE_DARK
+        };
+    }
+    asset
+}
+
+/// A round bumper on a wall colored background.
+fn bumper_asset() -> [u8; TILE_SIZE] {
+    let mut asset = WALL_TILE_ASSET;
+    for (i, pixel) in asset.iter_mut().enumerate() {
+        let (x, y) = ((i % 16) as f64 - 7.5, (i / 16) as f64 - 7.5);
+        let distance = (x * x + y * y).sqrt();
+        if distance < 4.0 {
+            *pixel = BUMPER_INNER;
+        } else if distance < 8.0 {
+            *pixel = BUMPER_OUTER;
+        }
+    }
+    asset
+}
+
+/// `background` with chevrons of `arrow` pointing towards `facing`. Used for
+/// boosters and one-way walls.
+fn arrow_asset(facing: Facing, background: [u8; TILE_SIZE], arrow: u8) -> [u8; TILE_SIZE] {
+    let unit = facing.unit();
+    let mut asset = background;
+    for (i, pixel) in asset.iter_mut().enumerate() {
+        let (x, y) = ((i % 16) as f64 - 7.5, (i / 16) as f64 - 7.5);
+        let along = x * unit.x + y * unit.y;
+        let across = (x * unit.y - y * unit.x).abs();
+        if across < 6.0 && (along + across).rem_euclid(8.0) < 3.0 {
+            *pixel = arrow;
+        }
+    }
+    asset
+}
+
+/// A gate, solid bars when closed and only its posts when open.
+fn gate_asset(open: bool) -> [u8; TILE_SIZE] {
+    let mut asset = GROUND_TILE_ASSET;
+    for (i, pixel) in asset.iter_mut().enumerate() {
+        let (x, y) = (i % 16, i / 16);
+        let (inner_x, inner_y) = ((2..14).contains(&x), (2..14).contains(&y));
+        let post = !inner_x && !inner_y;
+        let bar = !open && (x % 4 < 2 || !inner_y);
+        if post || bar {
+            *pixel = GATE;
+        }
+    }
+    asset
+}
+
+/// Ground with a square pad in the middle.
+fn switch_asset() -> [u8; TILE_SIZE] {
+    let mut asset = GROUND_TILE_ASSET;
+    for (i, pixel) in asset.iter_mut().enumerate() {
+        let (x, y) = (i % 16, i / 16);
+        if (4..12).contains(&x) && (4..12).contains(&y) {
+            *pixel = SWITCH;
+        }
+    }
+    asset
+}
+
+/// A belt with slats across it every few pixels, closer together on faster
+/// conveyors.
+fn conveyor_asset(facing: Facing, speed: u8) -> [u8; TILE_SIZE] {
+    let unit = facing.unit();
+    let gap = 8.0 / speed.max(1) as f64;
+    let mut asset = [CONVEYOR_BELT; TILE_SIZE];
+    for (i, pixel) in asset.iter_mut().enumerate() {
+        let (x, y) = ((i % 16) as f64 + 0.5, (i / 16) as f64 + 0.5);
+        if (x * unit.x + y * unit.y).rem_euclid(gap) < 1.0 {
+            *pixel = CONVEYOR_SLAT;
+        }
+    }
+    asset
 }
 
 #[derive(Clone, Debug)]
@@ -15,38 +457,31 @@ pub struct Tile {
     loc: Point,
     sprite: Sprite,
     tile_type: TileType,
-    reflect: bool,
+    restitution: f64,
     friction: f64,
 }
 impl Tile {
     pub fn new(x: usize, y: usize, tile_type: TileType) -> Self {
-        let reflect: bool;
-        let friction: f64;
-        let asset_template: [u8; TILE_SIZE];
-
         let point = Point::new(x, y);
 
-        match tile_type {
-            TileType::Ground => {
-                friction = 0.99980;
-                reflect = false;
-                asset_template = GROUND_TILE_ASSET;
-            }
-            TileType::Wall => {
-                friction = 0.99980;
-                reflect = true;
-                asset_template = WALL_TILE_ASSET;
-            }
-            TileType::Portal => {
-                friction = 0.99980;
-                reflect = false;
-                asset_template = PORTAL_TILE_ASSET;
-            }
-            TileType::Hole => {
-                friction = 0.99980;
-                reflect = false;
-                asset_template = HOLE_TILE_ASSET;
+        let asset_template: [u8; TILE_SIZE] = match tile_type {
+            TileType::Ground => GROUND_TILE_ASSET,
+            TileType::Wall => WALL_TILE_ASSET,
+            TileType::DiagonalWall(_) | TileType::CurvedWall(_) | TileType::RoundedWall(_) => {
+                shaped_asset(tile_type.shape())
             }
+            TileType::Slope { downhill, strength } => slope_asset(downhill, strength),
+            TileType::Portal => PORTAL_TILE_ASSET,
+            TileType::Hole => HOLE_TILE_ASSET,
+            TileType::Sand => SAND_TILE_ASSET,
+            TileType::Water => WATER_TILE_ASSET,
+            TileType::Void => VOID_TILE_ASSET,
+            TileType::Bumper => bumper_asset(),
+            TileType::Booster { facing } => arrow_asset(facing, GROUND_TILE_ASSET, BOOSTER_ARROW),
+            TileType::Conveyor { facing, speed } => conveyor_asset(facing, speed),
+            TileType::Gate { open, .. } => gate_asset(open),
+            TileType::Switch { .. } => switch_asset(),
+            TileType::OneWay { facing } => arrow_asset(facing, WALL_TILE_ASSET, 0),
         };
         let bitmap = map_color_to_rgba(&asset_template);
         let sprite = Sprite {
@@ -56,35 +491,64 @@ impl Tile {
         };
         Tile {
             loc: point,
-            sprite: sprite,
-            tile_type: tile_type,
-            friction: friction,
-            reflect: reflect,
+            sprite,
+            tile_type,
+            friction: tile_type.default_friction(),
+            restitution: tile_type.default_restitution(),
         }
     }
-    fn draw(&self, frame: &mut [u8]) {
-        blit(frame, &self.loc, &self.sprite);
+    fn draw(&self, frame: &mut [u8], camera: &Camera) {
+        let (x, y) = camera.offset();
+        blit(
+            frame,
+            &camera.view(),
+            (self.loc.x as i64 - x, self.loc.y as i64 - y),
+            &self.sprite,
+        );
     }
     pub fn get_type(&self) -> &TileType {
         &self.tile_type
     }
+    /// Fraction of its speed a ball rolling on this tile keeps each second.
+    pub fn friction(&self) -> f64 {
+        self.friction
+    }
+    /// Fraction of its speed into the tile a ball keeps when bouncing off it.
+    pub fn restitution(&self) -> f64 {
+        self.restitution
+    }
 }
 
+#[derive(Clone, Debug)]
 pub struct TileGrid {
     grid: Vec<Vec<Tile>>,
     xsize: usize,
     ysize: usize,
 }
 
+impl Default for TileGrid {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TileGrid {
     pub fn set_tile(&mut self, x: usize, y: usize, tile: Tile) {
         self.grid[y][x] = tile;
     }
 
-    pub fn draw(&self, frame: &mut [u8]) {
-        for row in self.grid.iter() {
-            for tile in row.iter() {
-                tile.draw(frame);
+    /// Draws the tiles `camera` can see.
+    pub fn draw(&self, frame: &mut [u8], camera: &Camera) {
+        let (x, y) = camera.offset();
+        let visible = |from: i64, size: f64, tiles: usize| {
+            let first = from.div_euclid(16).clamp(0, tiles as i64) as usize;
+            let last = (from + size.ceil() as i64 + 15).div_euclid(16);
+            first..last.clamp(0, tiles as i64) as usize
+        };
+        let columns = visible(x, camera.size.x, self.xsize);
+        for row in self.grid[visible(y, camera.size.y, self.ysize)].iter() {
+            for tile in row[columns.clone()].iter() {
+                tile.draw(frame, camera);
             }
         }
     }
@@ -101,6 +565,81 @@ impl TileGrid {
         &self.grid[y_index][x_index]
     }
 
+    /// Sets the friction and restitution of every tile of `tile_type`.
+    /// `None` leaves that value as it is.
+    pub fn configure(
+        &mut self,
+        tile_type: TileType,
+        friction: Option<f64>,
+        restitution: Option<f64>,
+    ) {
+        for tile in self.grid.iter_mut().flatten() {
+            if tile.tile_type != tile_type {
+                continue;
+            }
+            if let Some(friction) = friction {
+                tile.friction = friction;
+            }
+            if let Some(restitution) = restitution {
+                tile.restitution = restitution;
+            }
+        }
+    }
+
+    /// Opens every closed gate `id` and closes every open one.
+    pub fn toggle_gates(&mut self, id: u8) {
+        for tile in self.grid.iter_mut().flatten() {
+            let TileType::Gate { id: gate, open } = tile.tile_type else {
+                continue;
+            };
+            if gate != id {
+                continue;
+            }
+            let toggled = Tile::new(tile.loc.x, tile.loc.y, TileType::Gate { id, open: !open });
+            *tile = Tile {
+                friction: tile.friction,
+                restitution: tile.restitution,
+                ..toggled
+            };
+        }
+    }
+
+    /// The direction a one-way wall at column `x` and row `y` lets the ball
+    /// through in, if there is one.
+    pub fn one_way_at(&self, x: isize, y: isize) -> Option<Facing> {
+        if x < 0 || y < 0 || x as usize >= self.xsize || y as usize >= self.ysize {
+            return None;
+        }
+        match self.grid[y as usize][x as usize].tile_type {
+            TileType::OneWay { facing } => Some(facing),
+            _ => None,
+        }
+    }
+
+    /// Restitution of the tile at column `x` and row `y`. Off the grid acts
+    /// like a wall.
+    pub fn restitution_at(&self, x: isize, y: isize) -> f64 {
+        if x < 0 || y < 0 || x as usize >= self.xsize || y as usize >= self.ysize {
+            return TileType::Wall.default_restitution();
+        }
+        self.grid[y as usize][x as usize].restitution
+    }
+
+    /// Whether the tile at column `x` and row `y` stops the ball. Everything
+    /// off the grid does.
+    pub fn is_solid(&self, x: isize, y: isize) -> bool {
+        self.shape_at(x, y) != TileShape::Empty
+    }
+
+    /// The solid part of the tile at column `x` and row `y`. Everything off
+    /// the grid is a full wall.
+    pub fn shape_at(&self, x: isize, y: isize) -> TileShape {
+        if x < 0 || y < 0 || x as usize >= self.xsize || y as usize >= self.ysize {
+            return TileShape::Full;
+        }
+        self.grid[y as usize][x as usize].tile_type.shape()
+    }
+
     pub fn block_at(&self, x: usize, y: usize) -> &Tile {
         //Needed to integrate with the terminal numbering
         &self.grid[y + 1][x]
@@ -114,984 +653,23 @@ impl TileGrid {
         self.ysize
     }
     pub fn new() -> TileGrid {
-        let grid = map1();
-        let grid = TileGrid {
-            grid: grid,
-            xsize: 40,
-            ysize: 23,
-        };
-        return grid;
+        Level::map1().map.tile_grid
     }
-}
 
-fn map1() -> Vec<Vec<Tile>> {
-    let map1 = vec![
-        vec![
-            Tile::new(0, 0, TileType::Wall),
-            Tile::new(16, 0, TileType::Wall),
-            Tile::new(32, 0, TileType::Wall),
-            Tile::new(48, 0, TileType::Wall),
-            Tile::new(64, 0, TileType::Wall),
-            Tile::new(80, 0, TileType::Wall),
-            Tile::new(96, 0, TileType::Wall),
-            Tile::new(112, 0, TileType::Wall),
-            Tile::new(128, 0, TileType::Wall),
-            Tile::new(144, 0, TileType::Wall),
-            Tile::new(160, 0, TileType::Wall),
-            Tile::new(176, 0, TileType::Wall),
-            Tile::new(192, 0, TileType::Wall),
-            Tile::new(208, 0, TileType::Wall),
-            Tile::new(224, 0, TileType::Wall),
-            Tile::new(240, 0, TileType::Wall),
-            Tile::new(256, 0, TileType::Wall),
-            Tile::new(272, 0, TileType::Wall),
-            Tile::new(288, 0, TileType::Wall),
-            Tile::new(304, 0, TileType::Wall),
-            Tile::new(320, 0, TileType::Wall),
-            Tile::new(336, 0, TileType::Wall),
-            Tile::new(352, 0, TileType::Wall),
-            Tile::new(368, 0, TileType::Wall),
-            Tile::new(384, 0, TileType::Wall),
-            Tile::new(400, 0, TileType::Wall),
-            Tile::new(416, 0, TileType::Wall),
-            Tile::new(432, 0, TileType::Wall),
-            Tile::new(448, 0, TileType::Wall),
-            Tile::new(464, 0, TileType::Wall),
-            Tile::new(480, 0, TileType::Wall),
-            Tile::new(496, 0, TileType::Wall),
-            Tile::new(512, 0, TileType::Wall),
-            Tile::new(528, 0, TileType::Wall),
-            Tile::new(544, 0, TileType::Wall),
-            Tile::new(560, 0, TileType::Wall),
-            Tile::new(576, 0, TileType::Wall),
-            Tile::new(592, 0, TileType::Wall),
-            Tile::new(608, 0, TileType::Wall),
-            Tile::new(624, 0, TileType::Wall),
-        ],
-        vec![
-            Tile::new(0, 16, TileType::Wall),
-            Tile::new(16, 16, TileType::Ground),
-            Tile::new(32, 16, TileType::Ground),
-            Tile::new(48, 16, TileType::Ground),
-            Tile::new(64, 16, TileType::Ground),
-            Tile::new(80, 16, TileType::Ground),
-            Tile::new(96, 16, TileType::Ground),
-            Tile::new(112, 16, TileType::Ground),
-            Tile::new(128, 16, TileType::Ground),
-            Tile::new(144, 16, TileType::Ground),
-            Tile::new(160, 16, TileType::Ground),
-            Tile::new(176, 16, TileType::Ground),
-            Tile::new(192, 16, TileType::Ground),
-            Tile::new(208, 16, TileType::Ground),
-            Tile::new(224, 16, TileType::Ground),
-            Tile::new(240, 16, TileType::Ground),
-            Tile::new(256, 16, TileType::Ground),
-            Tile::new(272, 16, TileType::Ground),
-            Tile::new(288, 16, TileType::Ground),
-            Tile::new(304, 16, TileType::Ground),
-            Tile::new(320, 16, TileType::Ground),
-            Tile::new(336, 16, TileType::Ground),
-            Tile::new(352, 16, TileType::Ground),
-            Tile::new(368, 16, TileType::Ground),
-            Tile::new(384, 16, TileType::Ground),
-            Tile::new(400, 16, TileType::Ground),
-            Tile::new(416, 16, TileType::Ground),
-            Tile::new(432, 16, TileType::Ground),
-            Tile::new(448, 16, TileType::Ground),
-            Tile::new(464, 16, TileType::Wall),
-            Tile::new(480, 16, TileType::Ground),
-            Tile::new(496, 16, TileType::Ground),
-            Tile::new(512, 16, TileType::Ground),
-            Tile::new(528, 16, TileType::Ground),
-            Tile::new(544, 16, TileType::Ground),
-            Tile::new(560, 16, TileType::Ground),
-            Tile::new(576, 16, TileType::Ground),
-            Tile::new(592, 16, TileType::Ground),
-            Tile::new(608, 16, TileType::Ground),
-            Tile::new(624, 16, TileType::Wall),
-        ],
-        vec![
-            Tile::new(0, 32, TileType::Wall),
-            Tile::new(16, 32, TileType::Ground),
-            Tile::new(32, 32, TileType::Ground),
-            Tile::new(48, 32, TileType::Ground),
-            Tile::new(64, 32, TileType::Ground),
-            Tile::new(80, 32, TileType::Ground),
-            Tile::new(96, 32, TileType::Ground),
-            Tile::new(112, 32, TileType::Ground),
-            Tile::new(128, 32, TileType::Ground),
-            Tile::new(144, 32, TileType::Ground),
-            Tile::new(160, 32, TileType::Ground),
-            Tile::new(176, 32, TileType::Ground),
-            Tile::new(192, 32, TileType::Ground),
-            Tile::new(208, 32, TileType::Ground),
-            Tile::new(224, 32, TileType::Ground),
-            Tile::new(240, 32, TileType::Ground),
-            Tile::new(256, 32, TileType::Ground),
-            Tile::new(272, 32, TileType::Ground),
-            Tile::new(288, 32, TileType::Ground),
-            Tile::new(304, 32, TileType::Ground),
-            Tile::new(320, 32, TileType::Ground),
-            Tile::new(336, 32, TileType::Ground),
-            Tile::new(352, 32, TileType::Ground),
-            Tile::new(368, 32, TileType::Ground),
-            Tile::new(384, 32, TileType::Ground),
-            Tile::new(400, 32, TileType::Ground),
-            Tile::new(416, 32, TileType::Ground),
-            Tile::new(432, 32, TileType::Ground),
-            Tile::new(448, 32, TileType::Ground),
-            Tile::new(464, 32, TileType::Wall),
-            Tile::new(480, 32, TileType::Ground),
-            Tile::new(496, 32, TileType::Ground),
-            Tile::new(512, 32, TileType::Ground),
-            Tile::new(528, 32, TileType::Ground),
-            Tile::new(544, 32, TileType::Ground),
-            Tile::new(560, 32, TileType::Ground),
-            Tile::new(576, 32, TileType::Ground),
-            Tile::new(592, 32, TileType::Ground),
-            Tile::new(608, 32, TileType::Ground),
-            Tile::new(624, 32, TileType::Wall),
-        ],
-        vec![
-            Tile::new(0, 48, TileType::Wall),
-            Tile::new(16, 48, TileType::Ground),
-            Tile::new(32, 48, TileType::Ground),
-            Tile::new(48, 48, TileType::Ground),
-            Tile::new(64, 48, TileType::Ground),
-            Tile::new(80, 48, TileType::Ground),
-            Tile::new(96, 48, TileType::Ground),
-            Tile::new(112, 48, TileType::Ground),
-            Tile::new(128, 48, TileType::Ground),
-            Tile::new(144, 48, TileType::Ground),
-            Tile::new(160, 48, TileType::Ground),
-            Tile::new(176, 48, TileType::Ground),
-            Tile::new(192, 48, TileType::Ground),
-            Tile::new(208, 48, TileType::Ground),
-            Tile::new(224, 48, TileType::Ground),
-            Tile::new(240, 48, TileType::Ground),
-            Tile::new(256, 48, TileType::Ground),
-            Tile::new(272, 48, TileType::Ground),
-            Tile::new(288, 48, TileType::Ground),
-            Tile::new(304, 48, TileType::Ground),
-            Tile::new(320, 48, TileType::Ground),
-            Tile::new(336, 48, TileType::Ground),
-            Tile::new(352, 48, TileType::Ground),
-            Tile::new(368, 48, TileType::Ground),
-            Tile::new(384, 48, TileType::Ground),
-            Tile::new(400, 48, TileType::Ground),
-            Tile::new(416, 48, TileType::Ground),
-            Tile::new(432, 48, TileType::Ground),
-            Tile::new(448, 48, TileType::Ground),
-            Tile::new(464, 48, TileType::Ground),
-            Tile::new(480, 48, TileType::Ground),
-            Tile::new(496, 48, TileType::Ground),
-            Tile::new(512, 48, TileType::Ground),
-            Tile::new(528, 48, TileType::Ground),
-            Tile::new(544, 48, TileType::Ground),
-            Tile::new(560, 48, TileType::Ground),
-            Tile::new(576, 48, TileType::Ground),
-            Tile::new(592, 48, TileType::Ground),
-            Tile::new(608, 48, TileType::Ground),
-            Tile::new(624, 48, TileType::Wall),
-        ],
-        vec![
-            Tile::new(0, 64, TileType::Wall),
-            Tile::new(16, 64, TileType::Wall),
-            Tile::new(32, 64, TileType::Wall),
-            Tile::new(48, 64, TileType::Wall),
-            Tile::new(64, 64, TileType::Wall),
-            Tile::new(80, 64, TileType::Wall),
-            Tile::new(96, 64, TileType::Wall),
-            Tile::new(112, 64, TileType::Wall),
-            Tile::new(128, 64, TileType::Wall),
-            Tile::new(144, 64, TileType::Wall),
-            Tile::new(160, 64, TileType::Wall),
-            Tile::new(176, 64, TileType::Wall),
-            Tile::new(192, 64, TileType::Wall),
-            Tile::new(208, 64, TileType::Wall),
-            Tile::new(224, 64, TileType::Ground),
-            Tile::new(240, 64, TileType::Ground),
-            Tile::new(256, 64, TileType::Ground),
-            Tile::new(272, 64, TileType::Ground),
-            Tile::new(288, 64, TileType::Ground),
-            Tile::new(304, 64, TileType::Ground),
-            Tile::new(320, 64, TileType::Ground),
-            Tile::new(336, 64, TileType::Ground),
-            Tile::new(352, 64, TileType::Wall),
-            Tile::new(368, 64, TileType::Ground),
-            Tile::new(384, 64, TileType::Ground),
-            Tile::new(400, 64, TileType::Ground),
-            Tile::new(416, 64, TileType::Ground),
-            Tile::new(432, 64, TileType::Ground),
-            Tile::new(448, 64, TileType::Ground),
-            Tile::new(464, 64, TileType::Ground),
-            Tile::new(480, 64, TileType::Ground),
-            Tile::new(496, 64, TileType::Ground),
-            Tile::new(512, 64, TileType::Ground),
-            Tile::new(528, 64, TileType::Ground),
-            Tile::new(544, 64, TileType::Ground),
-            Tile::new(560, 64, TileType::Ground),
-            Tile::new(576, 64, TileType::Ground),
-            Tile::new(592, 64, TileType::Ground),
-            Tile::new(608, 64, TileType::Ground),
-            Tile::new(624, 64, TileType::Wall),
-        ],
-        vec![
-            Tile::new(0, 80, TileType::Wall),
-            Tile::new(16, 80, TileType::Ground),
-            Tile::new(32, 80, TileType::Ground),
-            Tile::new(48, 80, TileType::Ground),
-            Tile::new(64, 80, TileType::Ground),
-            Tile::new(80, 80, TileType::Ground),
-            Tile::new(96, 80, TileType::Ground),
-            Tile::new(112, 80, TileType::Ground),
-            Tile::new(128, 80, TileType::Ground),
-            Tile::new(144, 80, TileType::Ground),
-            Tile::new(160, 80, TileType::Ground),
-            Tile::new(176, 80, TileType::Ground),
-            Tile::new(192, 80, TileType::Ground),
-            Tile::new(208, 80, TileType::Ground),
-            Tile::new(224, 80, TileType::Ground),
-            Tile::new(240, 80, TileType::Ground),
-            Tile::new(256, 80, TileType::Ground),
-            Tile::new(272, 80, TileType::Ground),
-            Tile::new(288, 80, TileType::Ground),
-            Tile::new(304, 80, TileType::Ground),
-            Tile::new(320, 80, TileType::Ground),
-            Tile::new(336, 80, TileType::Ground),
-            Tile::new(352, 80, TileType::Wall),
-            Tile::new(368, 80, TileType::Ground),
-            Tile::new(384, 80, TileType::Ground),
-            Tile::new(400, 80, TileType::Ground),
-            Tile::new(416, 80, TileType::Ground),
-            Tile::new(432, 80, TileType::Ground),
-            Tile::new(448, 80, TileType::Ground),
-            Tile::new(464, 80, TileType::Ground),
-            Tile::new(480, 80, TileType::Ground),
-            Tile::new(496, 80, TileType::Ground),
-            Tile::new(512, 80, TileType::Ground),
-            Tile::new(528, 80, TileType::Ground),
-            Tile::new(544, 80, TileType::Ground),
-            Tile::new(560, 80, TileType::Ground),
-            Tile::new(576, 80, TileType::Ground),
-            Tile::new(592, 80, TileType::Ground),
-            Tile::new(608, 80, TileType::Ground),
-            Tile::new(624, 80, TileType::Wall),
-        ],
-        vec![
-            Tile::new(0, 96, TileType::Wall),
-            Tile::new(16, 96, TileType::Ground),
-            Tile::new(32, 96, TileType::Portal),
-            Tile::new(48, 96, TileType::Ground),
-            Tile::new(64, 96, TileType::Ground),
-            Tile::new(80, 96, TileType::Ground),
-            Tile::new(96, 96, TileType::Ground),
-            Tile::new(112, 96, TileType::Ground),
-            Tile::new(128, 96, TileType::Ground),
-            Tile::new(144, 96, TileType::Ground),
-            Tile::new(160, 96, TileType::Ground),
-            Tile::new(176, 96, TileType::Ground),
-            Tile::new(192, 96, TileType::Ground),
-            Tile::new(208, 96, TileType::Ground),
-            Tile::new(224, 96, TileType::Ground),
-            Tile::new(240, 96, TileType::Ground),
-            Tile::new(256, 96, TileType::Ground),
-            Tile::new(272, 96, TileType::Ground),
-            Tile::new(288, 96, TileType::Ground),
-            Tile::new(304, 96, TileType::Ground),
-            Tile::new(320, 96, TileType::Ground),
-            Tile::new(336, 96, TileType::Ground),
-            Tile::new(352, 96, TileType::Wall),
-            Tile::new(368, 96, TileType::Ground),
-            Tile::new(384, 96, TileType::Ground),
-            Tile::new(400, 96, TileType::Ground),
-            Tile::new(416, 96, TileType::Ground),
-            Tile::new(432, 96, TileType::Ground),
-            Tile::new(448, 96, TileType::Ground),
-            Tile::new(464, 96, TileType::Wall),
-            Tile::new(480, 96, TileType::Ground),
-            Tile::new(496, 96, TileType::Ground),
-            Tile::new(512, 96, TileType::Ground),
-            Tile::new(528, 96, TileType::Ground),
-            Tile::new(544, 96, TileType::Ground),
-            Tile::new(560, 96, TileType::Ground),
-            Tile::new(576, 96, TileType::Ground),
-            Tile::new(592, 96, TileType::Ground),
-            Tile::new(608, 96, TileType::Ground),
-            Tile::new(624, 96, TileType::Wall),
-        ],
-        vec![
-            Tile::new(0, 112, TileType::Wall),
-            Tile::new(16, 112, TileType::Ground),
-            Tile::new(32, 112, TileType::Ground),
-            Tile::new(48, 112, TileType::Ground),
-            Tile::new(64, 112, TileType::Ground),
-            Tile::new(80, 112, TileType::Ground),
-            Tile::new(96, 112, TileType::Ground),
-            Tile::new(112, 112, TileType::Ground),
-            Tile::new(128, 112, TileType::Ground),
-            Tile::new(144, 112, TileType::Ground),
-            Tile::new(160, 112, TileType::Ground),
-            Tile::new(176, 112, TileType::Ground),
-            Tile::new(192, 112, TileType::Ground),
-            Tile::new(208, 112, TileType::Ground),
-            Tile::new(224, 112, TileType::Ground),
-            Tile::new(240, 112, TileType::Ground),
-            Tile::new(256, 112, TileType::Ground),
-            Tile::new(272, 112, TileType::Ground),
-            Tile::new(288, 112, TileType::Ground),
-            Tile::new(304, 112, TileType::Ground),
-            Tile::new(320, 112, TileType::Ground),
-            Tile::new(336, 112, TileType::Ground),
-            Tile::new(352, 112, TileType::Wall),
-            Tile::new(368, 112, TileType::Ground),
-            Tile::new(384, 112, TileType::Ground),
-            Tile::new(400, 112, TileType::Ground),
-            Tile::new(416, 112, TileType::Ground),
-            Tile::new(432, 112, TileType::Ground),
-            Tile::new(448, 112, TileType::Ground),
-            Tile::new(464, 112, TileType::Wall),
-            Tile::new(480, 112, TileType::Ground),
-            Tile::new(496, 112, TileType::Ground),
-            Tile::new(512, 112, TileType::Ground),
-            Tile::new(528, 112, TileType::Ground),
-            Tile::new(544, 112, TileType::Ground),
-            Tile::new(560, 112, TileType::Ground),
-            Tile::new(576, 112, TileType::Ground),
-            Tile::new(592, 112, TileType::Ground),
-            Tile::new(608, 112, TileType::Ground),
-            Tile::new(624, 112, TileType::Wall),
-        ],
-        vec![
-            Tile::new(0, 128, TileType::Wall),
-            Tile::new(16, 128, TileType::Wall),
-            Tile::new(32, 128, TileType::Wall),
-            Tile::new(48, 128, TileType::Wall),
-            Tile::new(64, 128, TileType::Wall),
-            Tile::new(80, 128, TileType::Wall),
-            Tile::new(96, 128, TileType::Wall),
-            Tile::new(112, 128, TileType::Wall),
-            Tile::new(128, 128, TileType::Wall),
-            Tile::new(144, 128, TileType::Wall),
-            Tile::new(160, 128, TileType::Wall),
-            Tile::new(176, 128, TileType::Wall),
-            Tile::new(192, 128, TileType::Wall),
-            Tile::new(208, 128, TileType::Wall),
-            Tile::new(224, 128, TileType::Wall),
-            Tile::new(240, 128, TileType::Wall),
-            Tile::new(256, 128, TileType::Wall),
-            Tile::new(272, 128, TileType::Wall),
-            Tile::new(288, 128, TileType::Wall),
-            Tile::new(304, 128, TileType::Wall),
-            Tile::new(320, 128, TileType::Wall),
-            Tile::new(336, 128, TileType::Wall),
-            Tile::new(352, 128, TileType::Wall),
-            Tile::new(368, 128, TileType::Wall),
-            Tile::new(384, 128, TileType::Wall),
-            Tile::new(400, 128, TileType::Wall),
-            Tile::new(416, 128, TileType::Wall),
-            Tile::new(432, 128, TileType::Wall),
-            Tile::new(448, 128, TileType::Wall),
-            Tile::new(464, 128, TileType::Wall),
-            Tile::new(480, 128, TileType::Ground),
-            Tile::new(496, 128, TileType::Ground),
-            Tile::new(512, 128, TileType::Ground),
-            Tile::new(528, 128, TileType::Ground),
-            Tile::new(544, 128, TileType::Ground),
-            Tile::new(560, 128, TileType::Ground),
-            Tile::new(576, 128, TileType::Ground),
-            Tile::new(592, 128, TileType::Wall),
-            Tile::new(608, 128, TileType::Wall),
-            Tile::new(624, 128, TileType::Wall),
-        ],
-        vec![
-            Tile::new(0, 144, TileType::Wall),
-            Tile::new(16, 144, TileType::Ground),
-            Tile::new(32, 144, TileType::Ground),
-            Tile::new(48, 144, TileType::Ground),
-            Tile::new(64, 144, TileType::Ground),
-            Tile::new(80, 144, TileType::Ground),
-            Tile::new(96, 144, TileType::Ground),
-            Tile::new(112, 144, TileType::Ground),
-            Tile::new(128, 144, TileType::Ground),
-            Tile::new(144, 144, TileType::Ground),
-            Tile::new(160, 144, TileType::Wall),
-            Tile::new(176, 144, TileType::Ground),
-            Tile::new(192, 144, TileType::Ground),
-            Tile::new(208, 144, TileType::Ground),
-            Tile::new(224, 144, TileType::Ground),
-            Tile::new(240, 144, TileType::Ground),
-            Tile::new(256, 144, TileType::Ground),
-            Tile::new(272, 144, TileType::Ground),
-            Tile::new(288, 144, TileType::Ground),
-            Tile::new(304, 144, TileType::Ground),
-            Tile::new(320, 144, TileType::Ground),
-            Tile::new(336, 144, TileType::Ground),
-            Tile::new(352, 144, TileType::Ground),
-            Tile::new(368, 144, TileType::Ground),
-            Tile::new(384, 144, TileType::Ground),
-            Tile::new(400, 144, TileType::Ground),
-            Tile::new(416, 144, TileType::Ground),
-            Tile::new(432, 144, TileType::Ground),
-            Tile::new(448, 144, TileType::Ground),
-            Tile::new(464, 144, TileType::Wall),
-            Tile::new(480, 144, TileType::Ground),
-            Tile::new(496, 144, TileType::Ground),
-            Tile::new(512, 144, TileType::Ground),
-            Tile::new(528, 144, TileType::Ground),
-            Tile::new(544, 144, TileType::Ground),
-            Tile::new(560, 144, TileType::Ground),
-            Tile::new(576, 144, TileType::Ground),
-            Tile::new(592, 144, TileType::Ground),
-            Tile::new(608, 144, TileType::Ground),
-            Tile::new(624, 144, TileType::Wall),
-        ],
-        vec![
-            Tile::new(0, 160, TileType::Wall),
-            Tile::new(16, 160, TileType::Ground),
-            Tile::new(32, 160, TileType::Ground),
-            Tile::new(48, 160, TileType::Ground),
-            Tile::new(64, 160, TileType::Ground),
-            Tile::new(80, 160, TileType::Ground),
-            Tile::new(96, 160, TileType::Ground),
-            Tile::new(112, 160, TileType::Ground),
-            Tile::new(128, 160, TileType::Ground),
-            Tile::new(144, 160, TileType::Ground),
-            Tile::new(160, 160, TileType::Wall),
-            Tile::new(176, 160, TileType::Ground),
-            Tile::new(192, 160, TileType::Ground),
-            Tile::new(208, 160, TileType::Ground),
-            Tile::new(224, 160, TileType::Ground),
-            Tile::new(240, 160, TileType::Ground),
-            Tile::new(256, 160, TileType::Ground),
-            Tile::new(272, 160, TileType::Ground),
-            Tile::new(288, 160, TileType::Ground),
-            Tile::new(304, 160, TileType::Ground),
-            Tile::new(320, 160, TileType::Ground),
-            Tile::new(336, 160, TileType::Ground),
-            Tile::new(352, 160, TileType::Ground),
-            Tile::new(368, 160, TileType::Ground),
-            Tile::new(384, 160, TileType::Ground),
-            Tile::new(400, 160, TileType::Ground),
-            Tile::new(416, 160, TileType::Ground),
-            Tile::new(432, 160, TileType::Ground),
-            Tile::new(448, 160, TileType::Ground),
-            Tile::new(464, 160, TileType::Wall),
-            Tile::new(480, 160, TileType::Ground),
-            Tile::new(496, 160, TileType::Ground),
-            Tile::new(512, 160, TileType::Ground),
-            Tile::new(528, 160, TileType::Ground),
-            Tile::new(544, 160, TileType::Ground),
-            Tile::new(560, 160, TileType::Ground),
-            Tile::new(576, 160, TileType::Ground),
-            Tile::new(592, 160, TileType::Ground),
-            Tile::new(608, 160, TileType::Ground),
-            Tile::new(624, 160, TileType::Wall),
-        ],
-        vec![
-            Tile::new(0, 176, TileType::Wall),
-            Tile::new(16, 176, TileType::Ground),
-            Tile::new(32, 176, TileType::Ground),
-            Tile::new(48, 176, TileType::Ground),
-            Tile::new(64, 176, TileType::Ground),
-            Tile::new(80, 176, TileType::Ground),
-            Tile::new(96, 176, TileType::Ground),
-            Tile::new(112, 176, TileType::Ground),
-            Tile::new(128, 176, TileType::Ground),
-            Tile::new(144, 176, TileType::Ground),
-            Tile::new(160, 176, TileType::Wall),
-            Tile::new(176, 176, TileType::Ground),
-            Tile::new(192, 176, TileType::Ground),
-            Tile::new(208, 176, TileType::Ground),
-            Tile::new(224, 176, TileType::Ground),
-            Tile::new(240, 176, TileType::Ground),
-            Tile::new(256, 176, TileType::Ground),
-            Tile::new(272, 176, TileType::Ground),
-            Tile::new(288, 176, TileType::Ground),
-            Tile::new(304, 176, TileType::Ground),
-            Tile::new(320, 176, TileType::Ground),
-            Tile::new(336, 176, TileType::Ground),
-            Tile::new(352, 176, TileType::Ground),
-            Tile::new(368, 176, TileType::Ground),
-            Tile::new(384, 176, TileType::Ground),
-            Tile::new(400, 176, TileType::Ground),
-            Tile::new(416, 176, TileType::Ground),
-            Tile::new(432, 176, TileType::Ground),
-            Tile::new(448, 176, TileType::Ground),
-            Tile::new(464, 176, TileType::Wall),
-            Tile::new(480, 176, TileType::Ground),
-            Tile::new(496, 176, TileType::Ground),
-            Tile::new(512, 176, TileType::Ground),
-            Tile::new(528, 176, TileType::Ground),
-            Tile::new(544, 176, TileType::Ground),
-            Tile::new(560, 176, TileType::Ground),
-            Tile::new(576, 176, TileType::Ground),
-            Tile::new(592, 176, TileType::Ground),
-            Tile::new(608, 176, TileType::Ground),
-            Tile::new(624, 176, TileType::Wall),
-        ],
-        vec![
-            Tile::new(0, 192, TileType::Wall),
-            Tile::new(16, 192, TileType::Ground),
-            Tile::new(32, 192, TileType::Ground),
-            Tile::new(48, 192, TileType::Ground),
-            Tile::new(64, 192, TileType::Ground),
-            Tile::new(80, 192, TileType::Wall),
-            Tile::new(96, 192, TileType::Ground),
-            Tile::new(112, 192, TileType::Ground),
-            Tile::new(128, 192, TileType::Ground),
-            Tile::new(144, 192, TileType::Ground),
-            Tile::new(160, 192, TileType::Ground),
-            Tile::new(176, 192, TileType::Ground),
-            Tile::new(192, 192, TileType::Ground),
-            Tile::new(208, 192, TileType::Ground),
-            Tile::new(224, 192, TileType::Ground),
-            Tile::new(240, 192, TileType::Ground),
-            Tile::new(256, 192, TileType::Ground),
-            Tile::new(272, 192, TileType::Ground),
-            Tile::new(288, 192, TileType::Ground),
-            Tile::new(304, 192, TileType::Ground),
-            Tile::new(320, 192, TileType::Ground),
-            Tile::new(336, 192, TileType::Ground),
-            Tile::new(352, 192, TileType::Ground),
-            Tile::new(368, 192, TileType::Ground),
-            Tile::new(384, 192, TileType::Ground),
-            Tile::new(400, 192, TileType::Ground),
-            Tile::new(416, 192, TileType::Ground),
-            Tile::new(432, 192, TileType::Ground),
-            Tile::new(448, 192, TileType::Ground),
-            Tile::new(464, 192, TileType::Wall),
-            Tile::new(480, 192, TileType::Wall),
-            Tile::new(496, 192, TileType::Wall),
-            Tile::new(512, 192, TileType::Wall),
-            Tile::new(528, 192, TileType::Ground),
-            Tile::new(544, 192, TileType::Ground),
-            Tile::new(560, 192, TileType::Ground),
-            Tile::new(576, 192, TileType::Ground),
-            Tile::new(592, 192, TileType::Ground),
-            Tile::new(608, 192, TileType::Ground),
-            Tile::new(624, 192, TileType::Wall),
-        ],
-        vec![
-            Tile::new(0, 208, TileType::Wall),
-            Tile::new(16, 208, TileType::Ground),
-            Tile::new(32, 208, TileType::Portal),
-            Tile::new(48, 208, TileType::Ground),
-            Tile::new(64, 208, TileType::Ground),
-            Tile::new(80, 208, TileType::Wall),
-            Tile::new(96, 208, TileType::Ground),
-            Tile::new(112, 208, TileType::Ground),
-            Tile::new(128, 208, TileType::Ground),
-            Tile::new(144, 208, TileType::Ground),
-            Tile::new(160, 208, TileType::Ground),
-            Tile::new(176, 208, TileType::Ground),
-            Tile::new(192, 208, TileType::Ground),
-            Tile::new(208, 208, TileType::Ground),
-            Tile::new(224, 208, TileType::Ground),
-            Tile::new(240, 208, TileType::Wall),
-            Tile::new(256, 208, TileType::Ground),
-            Tile::new(272, 208, TileType::Ground),
-            Tile::new(288, 208, TileType::Ground),
-            Tile::new(304, 208, TileType::Ground),
-            Tile::new(320, 208, TileType::Ground),
-            Tile::new(336, 208, TileType::Ground),
-            Tile::new(352, 208, TileType::Ground),
-            Tile::new(368, 208, TileType::Ground),
-            Tile::new(384, 208, TileType::Ground),
-            Tile::new(400, 208, TileType::Ground),
-            Tile::new(416, 208, TileType::Ground),
-            Tile::new(432, 208, TileType::Ground),
-            Tile::new(448, 208, TileType::Ground),
-            Tile::new(464, 208, TileType::Wall),
-            Tile::new(480, 208, TileType::Ground),
-            Tile::new(496, 208, TileType::Ground),
-            Tile::new(512, 208, TileType::Ground),
-            Tile::new(528, 208, TileType::Ground),
-            Tile::new(544, 208, TileType::Ground),
-            Tile::new(560, 208, TileType::Ground),
-            Tile::new(576, 208, TileType::Ground),
-            Tile::new(592, 208, TileType::Ground),
-            Tile::new(608, 208, TileType::Ground),
-            Tile::new(624, 208, TileType::Wall),
-        ],
-        vec![
-            Tile::new(0, 224, TileType::Wall),
-            Tile::new(16, 224, TileType::Ground),
-            Tile::new(32, 224, TileType::Ground),
-            Tile::new(48, 224, TileType::Ground),
-            Tile::new(64, 224, TileType::Ground),
-            Tile::new(80, 224, TileType::Wall),
-            Tile::new(96, 224, TileType::Ground),
-            Tile::new(112, 224, TileType::Ground),
-            Tile::new(128, 224, TileType::Ground),
-            Tile::new(144, 224, TileType::Ground),
-            Tile::new(160, 224, TileType::Ground),
-            Tile::new(176, 224, TileType::Ground),
-            Tile::new(192, 224, TileType::Ground),
-            Tile::new(208, 224, TileType::Ground),
-            Tile::new(224, 224, TileType::Ground),
-            Tile::new(240, 224, TileType::Wall),
-            Tile::new(256, 224, TileType::Ground),
-            Tile::new(272, 224, TileType::Ground),
-            Tile::new(288, 224, TileType::Ground),
-            Tile::new(304, 224, TileType::Ground),
-            Tile::new(320, 224, TileType::Ground),
-            Tile::new(336, 224, TileType::Ground),
-            Tile::new(352, 224, TileType::Ground),
-            Tile::new(368, 224, TileType::Ground),
-            Tile::new(384, 224, TileType::Ground),
-            Tile::new(400, 224, TileType::Ground),
-            Tile::new(416, 224, TileType::Ground),
-            Tile::new(432, 224, TileType::Ground),
-            Tile::new(448, 224, TileType::Ground),
-            Tile::new(464, 224, TileType::Wall),
-            Tile::new(480, 224, TileType::Ground),
-            Tile::new(496, 224, TileType::Ground),
-            Tile::new(512, 224, TileType::Ground),
-            Tile::new(528, 224, TileType::Ground),
-            Tile::new(544, 224, TileType::Ground),
-            Tile::new(560, 224, TileType::Ground),
-            Tile::new(576, 224, TileType::Ground),
-            Tile::new(592, 224, TileType::Ground),
-            Tile::new(608, 224, TileType::Ground),
-            Tile::new(624, 224, TileType::Wall),
-        ],
-        vec![
-            Tile::new(0, 240, TileType::Wall),
-            Tile::new(16, 240, TileType::Wall),
-            Tile::new(32, 240, TileType::Wall),
-            Tile::new(48, 240, TileType::Wall),
-            Tile::new(64, 240, TileType::Wall),
-            Tile::new(80, 240, TileType::Wall),
-            Tile::new(96, 240, TileType::Wall),
-            Tile::new(112, 240, TileType::Wall),
-            Tile::new(128, 240, TileType::Wall),
-            Tile::new(144, 240, TileType::Wall),
-            Tile::new(160, 240, TileType::Wall),
-            Tile::new(176, 240, TileType::Wall),
-            Tile::new(192, 240, TileType::Wall),
-            Tile::new(208, 240, TileType::Wall),
-            Tile::new(224, 240, TileType::Wall),
-            Tile::new(240, 240, TileType::Wall),
-            Tile::new(256, 240, TileType::Ground),
-            Tile::new(272, 240, TileType::Ground),
-            Tile::new(288, 240, TileType::Ground),
-            Tile::new(304, 240, TileType::Ground),
-            Tile::new(320, 240, TileType::Ground),
-            Tile::new(336, 240, TileType::Ground),
-            Tile::new(352, 240, TileType::Ground),
-            Tile::new(368, 240, TileType::Ground),
-            Tile::new(384, 240, TileType::Wall),
-            Tile::new(400, 240, TileType::Ground),
-            Tile::new(416, 240, TileType::Ground),
-            Tile::new(432, 240, TileType::Ground),
-            Tile::new(448, 240, TileType::Ground),
-            Tile::new(464, 240, TileType::Ground),
-            Tile::new(480, 240, TileType::Ground),
-            Tile::new(496, 240, TileType::Ground),
-            Tile::new(512, 240, TileType::Ground),
-            Tile::new(528, 240, TileType::Ground),
-            Tile::new(544, 240, TileType::Ground),
-            Tile::new(560, 240, TileType::Ground),
-            Tile::new(576, 240, TileType::Ground),
-            Tile::new(592, 240, TileType::Ground),
-            Tile::new(608, 240, TileType::Ground),
-            Tile::new(624, 240, TileType::Wall),
-        ],
-        vec![
-            Tile::new(0, 256, TileType::Wall),
-            Tile::new(16, 256, TileType::Ground),
-            Tile::new(32, 256, TileType::Ground),
-            Tile::new(48, 256, TileType::Ground),
-            Tile::new(64, 256, TileType::Ground),
-            Tile::new(80, 256, TileType::Wall),
-            Tile::new(96, 256, TileType::Wall),
-            Tile::new(112, 256, TileType::Wall),
-            Tile::new(128, 256, TileType::Wall),
-            Tile::new(144, 256, TileType::Wall),
-            Tile::new(160, 256, TileType::Ground),
-            Tile::new(176, 256, TileType::Ground),
-            Tile::new(192, 256, TileType::Ground),
-            Tile::new(208, 256, TileType::Ground),
-            Tile::new(224, 256, TileType::Ground),
-            Tile::new(240, 256, TileType::Wall),
-            Tile::new(256, 256, TileType::Ground),
-            Tile::new(272, 256, TileType::Ground),
-            Tile::new(288, 256, TileType::Ground),
-            Tile::new(304, 256, TileType::Ground),
-            Tile::new(320, 256, TileType::Ground),
-            Tile::new(336, 256, TileType::Ground),
-            Tile::new(352, 256, TileType::Ground),
-            Tile::new(368, 256, TileType::Ground),
-            Tile::new(384, 256, TileType::Wall),
-            Tile::new(400, 256, TileType::Ground),
-            Tile::new(416, 256, TileType::Ground),
-            Tile::new(432, 256, TileType::Ground),
-            Tile::new(448, 256, TileType::Ground),
-            Tile::new(464, 256, TileType::Ground),
-            Tile::new(480, 256, TileType::Ground),
-            Tile::new(496, 256, TileType::Ground),
-            Tile::new(512, 256, TileType::Ground),
-            Tile::new(528, 256, TileType::Ground),
-            Tile::new(544, 256, TileType::Ground),
-            Tile::new(560, 256, TileType::Ground),
-            Tile::new(576, 256, TileType::Ground),
-            Tile::new(592, 256, TileType::Ground),
-            Tile::new(608, 256, TileType::Ground),
-            Tile::new(624, 256, TileType::Wall),
-        ],
-        vec![
-            Tile::new(0, 272, TileType::Wall),
-            Tile::new(16, 272, TileType::Ground),
-            Tile::new(32, 272, TileType::Ground),
-            Tile::new(48, 272, TileType::Ground),
-            Tile::new(64, 272, TileType::Ground),
-            Tile::new(80, 272, TileType::Ground),
-            Tile::new(96, 272, TileType::Ground),
-            Tile::new(112, 272, TileType::Wall),
-            Tile::new(128, 272, TileType::Ground),
-            Tile::new(144, 272, TileType::Ground),
-            Tile::new(160, 272, TileType::Ground),
-            Tile::new(176, 272, TileType::Ground),
-            Tile::new(192, 272, TileType::Ground),
-            Tile::new(208, 272, TileType::Ground),
-            Tile::new(224, 272, TileType::Ground),
-            Tile::new(240, 272, TileType::Wall),
-            Tile::new(256, 272, TileType::Ground),
-            Tile::new(272, 272, TileType::Ground),
-            Tile::new(288, 272, TileType::Ground),
-            Tile::new(304, 272, TileType::Ground),
-            Tile::new(320, 272, TileType::Ground),
-            Tile::new(336, 272, TileType::Ground),
-            Tile::new(352, 272, TileType::Ground),
-            Tile::new(368, 272, TileType::Ground),
-            Tile::new(384, 272, TileType::Wall),
-            Tile::new(400, 272, TileType::Ground),
-            Tile::new(416, 272, TileType::Ground),
-            Tile::new(432, 272, TileType::Ground),
-            Tile::new(448, 272, TileType::Ground),
-            Tile::new(464, 272, TileType::Ground),
-            Tile::new(480, 272, TileType::Ground),
-            Tile::new(496, 272, TileType::Ground),
-            Tile::new(512, 272, TileType::Ground),
-            Tile::new(528, 272, TileType::Ground),
-            Tile::new(544, 272, TileType::Ground),
-            Tile::new(560, 272, TileType::Ground),
-            Tile::new(576, 272, TileType::Ground),
-            Tile::new(592, 272, TileType::Ground),
-            Tile::new(608, 272, TileType::Ground),
-            Tile::new(624, 272, TileType::Wall),
-        ],
-        vec![
-            Tile::new(0, 288, TileType::Wall),
-            Tile::new(16, 288, TileType::Ground),
-            Tile::new(32, 288, TileType::Ground),
-            Tile::new(48, 288, TileType::Ground),
-            Tile::new(64, 288, TileType::Ground),
-            Tile::new(80, 288, TileType::Ground),
-            Tile::new(96, 288, TileType::Ground),
-            Tile::new(112, 288, TileType::Ground),
-            Tile::new(128, 288, TileType::Ground),
-            Tile::new(144, 288, TileType::Ground),
-            Tile::new(160, 288, TileType::Ground),
-            Tile::new(176, 288, TileType::Ground),
-            Tile::new(192, 288, TileType::Ground),
-            Tile::new(208, 288, TileType::Ground),
-            Tile::new(224, 288, TileType::Ground),
-            Tile::new(240, 288, TileType::Wall),
-            Tile::new(256, 288, TileType::Ground),
-            Tile::new(272, 288, TileType::Ground),
-            Tile::new(288, 288, TileType::Ground),
-            Tile::new(304, 288, TileType::Ground),
-            Tile::new(320, 288, TileType::Ground),
-            Tile::new(336, 288, TileType::Ground),
-            Tile::new(352, 288, TileType::Ground),
-            Tile::new(368, 288, TileType::Ground),
-            Tile::new(384, 288, TileType::Wall),
-            Tile::new(400, 288, TileType::Wall),
-            Tile::new(416, 288, TileType::Wall),
-            Tile::new(432, 288, TileType::Wall),
-            Tile::new(448, 288, TileType::Wall),
-            Tile::new(464, 288, TileType::Wall),
-            Tile::new(480, 288, TileType::Wall),
-            Tile::new(496, 288, TileType::Wall),
-            Tile::new(512, 288, TileType::Wall),
-            Tile::new(528, 288, TileType::Wall),
-            Tile::new(544, 288, TileType::Ground),
-            Tile::new(560, 288, TileType::Ground),
-            Tile::new(576, 288, TileType::Ground),
-            Tile::new(592, 288, TileType::Ground),
-            Tile::new(608, 288, TileType::Ground),
-            Tile::new(624, 288, TileType::Wall),
-        ],
-        vec![
-            Tile::new(0, 304, TileType::Wall),
-            Tile::new(16, 304, TileType::Ground),
-            Tile::new(32, 304, TileType::Ground),
-            Tile::new(48, 304, TileType::Hole),
-            Tile::new(64, 304, TileType::Ground),
-            Tile::new(80, 304, TileType::Ground),
-            Tile::new(96, 304, TileType::Ground),
-            Tile::new(112, 304, TileType::Ground),
-            Tile::new(128, 304, TileType::Ground),
-            Tile::new(144, 304, TileType::Ground),
-            Tile::new(160, 304, TileType::Ground),
-            Tile::new(176, 304, TileType::Ground),
-            Tile::new(192, 304, TileType::Ground),
-            Tile::new(208, 304, TileType::Ground),
-            Tile::new(224, 304, TileType::Ground),
-            Tile::new(240, 304, TileType::Ground),
-            Tile::new(256, 304, TileType::Ground),
-            Tile::new(272, 304, TileType::Ground),
-            Tile::new(288, 304, TileType::Ground),
-            Tile::new(304, 304, TileType::Ground),
-            Tile::new(320, 304, TileType::Ground),
-            Tile::new(336, 304, TileType::Ground),
-            Tile::new(352, 304, TileType::Ground),
-            Tile::new(368, 304, TileType::Ground),
-            Tile::new(384, 304, TileType::Wall),
-            Tile::new(400, 304, TileType::Ground),
-            Tile::new(416, 304, TileType::Ground),
-            Tile::new(432, 304, TileType::Ground),
-            Tile::new(448, 304, TileType::Ground),
-            Tile::new(464, 304, TileType::Ground),
-            Tile::new(480, 304, TileType::Ground),
-            Tile::new(496, 304, TileType::Ground),
-            Tile::new(512, 304, TileType::Ground),
-            Tile::new(528, 304, TileType::Ground),
-            Tile::new(544, 304, TileType::Ground),
-            Tile::new(560, 304, TileType::Ground),
-            Tile::new(576, 304, TileType::Ground),
-            Tile::new(592, 304, TileType::Ground),
-            Tile::new(608, 304, TileType::Ground),
-            Tile::new(624, 304, TileType::Wall),
-        ],
-        vec![
-            Tile::new(0, 320, TileType::Wall),
-            Tile::new(16, 320, TileType::Ground),
-            Tile::new(32, 320, TileType::Ground),
-            Tile::new(48, 320, TileType::Ground),
-            Tile::new(64, 320, TileType::Ground),
-            Tile::new(80, 320, TileType::Ground),
-            Tile::new(96, 320, TileType::Ground),
-            Tile::new(112, 320, TileType::Wall),
-            Tile::new(128, 320, TileType::Ground),
-            Tile::new(144, 320, TileType::Ground),
-            Tile::new(160, 320, TileType::Ground),
-            Tile::new(176, 320, TileType::Ground),
-            Tile::new(192, 320, TileType::Ground),
-            Tile::new(208, 320, TileType::Ground),
-            Tile::new(224, 320, TileType::Ground),
-            Tile::new(240, 320, TileType::Ground),
-            Tile::new(256, 320, TileType::Ground),
-            Tile::new(272, 320, TileType::Ground),
-            Tile::new(288, 320, TileType::Ground),
-            Tile::new(304, 320, TileType::Ground),
-            Tile::new(320, 320, TileType::Ground),
-            Tile::new(336, 320, TileType::Ground),
-            Tile::new(352, 320, TileType::Ground),
-            Tile::new(368, 320, TileType::Ground),
-            Tile::new(384, 320, TileType::Wall),
-            Tile::new(400, 320, TileType::Ground),
-            Tile::new(416, 320, TileType::Ground),
-            Tile::new(432, 320, TileType::Ground),
-            Tile::new(448, 320, TileType::Ground),
-            Tile::new(464, 320, TileType::Ground),
-            Tile::new(480, 320, TileType::Ground),
-            Tile::new(496, 320, TileType::Ground),
-            Tile::new(512, 320, TileType::Ground),
-            Tile::new(528, 320, TileType::Ground),
-            Tile::new(544, 320, TileType::Ground),
-            Tile::new(560, 320, TileType::Ground),
-            Tile::new(576, 320, TileType::Ground),
-            Tile::new(592, 320, TileType::Ground),
-            Tile::new(608, 320, TileType::Ground),
-            Tile::new(624, 320, TileType::Wall),
-        ],
-        vec![
-            Tile::new(0, 336, TileType::Wall),
-            Tile::new(16, 336, TileType::Ground),
-            Tile::new(32, 336, TileType::Ground),
-            Tile::new(48, 336, TileType::Ground),
-            Tile::new(64, 336, TileType::Ground),
-            Tile::new(80, 336, TileType::Wall),
-            Tile::new(96, 336, TileType::Wall),
-            Tile::new(112, 336, TileType::Wall),
-            Tile::new(128, 336, TileType::Wall),
-            Tile::new(144, 336, TileType::Wall),
-            Tile::new(160, 336, TileType::Ground),
-            Tile::new(176, 336, TileType::Ground),
-            Tile::new(192, 336, TileType::Ground),
-            Tile::new(208, 336, TileType::Ground),
-            Tile::new(224, 336, TileType::Ground),
-            Tile::new(240, 336, TileType::Ground),
-            Tile::new(256, 336, TileType::Ground),
-            Tile::new(272, 336, TileType::Ground),
-            Tile::new(288, 336, TileType::Ground),
-            Tile::new(304, 336, TileType::Ground),
-            Tile::new(320, 336, TileType::Ground),
-            Tile::new(336, 336, TileType::Ground),
-            Tile::new(352, 336, TileType::Ground),
-            Tile::new(368, 336, TileType::Ground),
-            Tile::new(384, 336, TileType::Wall),
-            Tile::new(400, 336, TileType::Ground),
-            Tile::new(416, 336, TileType::Ground),
-            Tile::new(432, 336, TileType::Ground),
-            Tile::new(448, 336, TileType::Ground),
-            Tile::new(464, 336, TileType::Ground),
-            Tile::new(480, 336, TileType::Ground),
-            Tile::new(496, 336, TileType::Ground),
-            Tile::new(512, 336, TileType::Ground),
-            Tile::new(528, 336, TileType::Ground),
-            Tile::new(544, 336, TileType::Ground),
-            Tile::new(560, 336, TileType::Ground),
-            Tile::new(576, 336, TileType::Ground),
-            Tile::new(592, 336, TileType::Ground),
-            Tile::new(608, 336, TileType::Ground),
-            Tile::new(624, 336, TileType::Wall),
-        ],
-        vec![
-            Tile::new(0, 352, TileType::Wall),
-            Tile::new(16, 352, TileType::Wall),
-            Tile::new(32, 352, TileType::Wall),
-            Tile::new(48, 352, TileType::Wall),
-            Tile::new(64, 352, TileType::Wall),
-            Tile::new(80, 352, TileType::Wall),
-            Tile::new(96, 352, TileType::Wall),
-            Tile::new(112, 352, TileType::Wall),
-            Tile::new(128, 352, TileType::Wall),
-            Tile::new(144, 352, TileType::Wall),
-            Tile::new(160, 352, TileType::Wall),
-            Tile::new(176, 352, TileType::Wall),
-            Tile::new(192, 352, TileType::Wall),
-            Tile::new(208, 352, TileType::Wall),
-            Tile::new(224, 352, TileType::Wall),
-            Tile::new(240, 352, TileType::Wall),
-            Tile::new(256, 352, TileType::Wall),
-            Tile::new(272, 352, TileType::Wall),
-            Tile::new(288, 352, TileType::Wall),
-            Tile::new(304, 352, TileType::Wall),
-            Tile::new(320, 352, TileType::Wall),
-            Tile::new(336, 352, TileType::Wall),
-            Tile::new(352, 352, TileType::Wall),
-            Tile::new(368, 352, TileType::Wall),
-            Tile::new(384, 352, TileType::Wall),
-            Tile::new(400, 352, TileType::Wall),
-            Tile::new(416, 352, TileType::Wall),
-            Tile::new(432, 352, TileType::Wall),
-            Tile::new(448, 352, TileType::Wall),
-            Tile::new(464, 352, TileType::Wall),
-            Tile::new(480, 352, TileType::Wall),
-            Tile::new(496, 352, TileType::Wall),
-            Tile::new(512, 352, TileType::Wall),
-            Tile::new(528, 352, TileType::Wall),
-            Tile::new(544, 352, TileType::Wall),
-            Tile::new(560, 352, TileType::Wall),
-            Tile::new(576, 352, TileType::Wall),
-            Tile::new(592, 352, TileType::Wall),
-            Tile::new(608, 352, TileType::Wall),
-            Tile::new(624, 352, TileType::Wall),
-        ],
-    ];
-    return map1;
+    /// Builds a grid from rows of tile types. Every row must be the same length.
+    pub fn from_rows(rows: &[Vec<TileType>]) -> TileGrid {
+        let grid: Vec<Vec<Tile>> = rows
+            .iter()
+            .enumerate()
+            .map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(x, tile_type)| Tile::new(x * 16, y * 16, *tile_type))
+                    .collect()
+            })
+            .collect();
+        let ysize = grid.len();
+        let xsize = grid.first().map_or(0, |row| row.len());
+        TileGrid { grid, xsize, ysize }
+    }
 }
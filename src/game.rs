@@ -1,34 +1,192 @@
-use std::clone;
 use std::rc::Rc;
 
+use crate::camera::*;
+use crate::common::*;
 use crate::controls::*;
+use crate::course::*;
 use crate::entities::ball::*;
+use crate::entities::obstacles::*;
 use crate::entities::tiles::*;
 use crate::geo::*;
+use crate::map::level::*;
 use crate::map::map::*;
+use crate::render::helpers::*;
+use crate::scoring::*;
 
-#[derive(PartialEq)]
+/// Distance in pixels from the center of a hole tile within which the ball
+/// is over the cup.
+const CUP_RADIUS: f64 = 8.0;
+/// Fastest speed, in pixels per second, a ball rolling straight over the
+/// middle of the cup drops in at.
+const CENTER_CAPTURE_SPEED: f64 = 220.0;
+/// Fastest speed a ball only clipping the edge of the cup drops in at.
+const EDGE_CAPTURE_SPEED: f64 = 60.0;
+/// Most a ball that lips out is turned by the rim, for one clipping the edge.
+const RIM_TURN: f64 = std::f64::consts::FRAC_PI_3;
+/// Fraction of its speed a ball keeps when it lips out.
+const RIM_KEEP: f64 = 0.8;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum GolfState {
     Aiming,
     Rolling,
     Hitting,
     InHole,
     Stop,
+    /// The last hole of the course has been sunk.
+    Finished,
+}
+
+/// Things that happened during an update, collected until they are taken
+/// with `GameState::take_events`. Holes are counted from zero.
+#[derive(Clone, Debug, PartialEq)]
+pub enum GameEvent {
+    Hit {
+        hole: usize,
+        stroke: u32,
+    },
+    Teleported {
+        from: Vec2<f64>,
+        to: Vec2<f64>,
+    },
+    /// The ball bounced off a surface facing `normal`.
+    Bounced {
+        normal: Vec2<f64>,
+    },
+    Stopped {
+        at: Vec2<f64>,
+    },
+    /// The ball was lost to a `hazard` and put back where it was last hit,
+    /// with a penalty stroke.
+    BallLost {
+        hazard: Hazard,
+        returned_to: Vec2<f64>,
+    },
+    Sunk {
+        hole: usize,
+        strokes: u32,
+    },
+    /// The ball crossed the cup too fast to drop and was turned by the rim.
+    LippedOut {
+        at: Vec2<f64>,
+    },
+    /// The ball rolled onto the switch at `column`, `row` and toggled its
+    /// gates.
+    Switched {
+        column: usize,
+        row: usize,
+    },
+    HoleStarted {
+        hole: usize,
+    },
+    CourseFinished,
+}
+
+/// Something that loses the ball.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Hazard {
+    /// A tile for which `TileType::is_hazard` holds.
+    Tile(TileType),
+    BlackHole,
 }
+
+#[derive(Clone, Debug)]
 pub struct GameState {
-    pub map: GameMap,
+    pub course: Course,
     pub state: GolfState,
     pub ball: Ball,
+    pub scorecard: Scorecard,
+    /// How far ahead the aiming guide looks.
+    pub preview: AimPreview,
+    /// The hole being played, with its gates as they are now.
+    map: GameMap,
+    /// The hole's moving obstacles, played from when the ball was teed up.
+    pub obstacles: Vec<Obstacle>,
+    /// Center of the ball when it was last hit. A lost ball goes back here.
+    last_rest: Vec2<f64>,
+    /// Whether the ball is over a cup it has already been judged against,
+    /// so a lip-out only turns it once.
+    over_cup: bool,
+    /// Whether the ball is on a switch it has already flipped.
+    on_switch: bool,
+    /// The part of the hole on screen.
+    pub camera: Camera,
+    events: Vec<GameEvent>,
+}
+
+impl Default for GameState {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl GameState {
     pub fn new() -> GameState {
+        GameState::from_course(Course::standard())
+    }
+    /// Starts a one hole game on `level`.
+    pub fn from_level(level: Level) -> GameState {
+        GameState::from_course(Course::new(vec![level]))
+    }
+    /// Starts a round on the first hole of `course`.
+    pub fn from_course(course: Course) -> GameState {
+        let tee = course.current().tee_point();
+        let ball = Ball::new_at_loc(tee.x, tee.y);
+        let camera = view_of(course.map(), &ball);
         GameState {
+            map: course.map().clone(),
+            obstacles: course.map().obstacles.clone(),
             state: GolfState::Aiming,
-            ball: Ball::new_at_loc(320, 48),
-            map: GameMap::new(),
+            preview: AimPreview::default(),
+            last_rest: ball.exact_center(),
+            over_cup: false,
+            on_switch: false,
+            camera,
+            ball,
+            scorecard: Scorecard::for_course(&course),
+            course,
+            events: Vec::new(),
         }
     }
+    /// The map of the hole being played, with its gates as they are now.
+    pub fn map(&self) -> &GameMap {
+        &self.map
+    }
+    /// Puts the ball back on the tee of the current hole and wipes its strokes.
+    pub fn restart_hole(&mut self) {
+        self.scorecard.reset_hole(self.course.current_index());
+        self.tee_up();
+    }
+    /// Starts the round again from the first hole with a blank scorecard.
+    pub fn restart_course(&mut self) {
+        self.course.restart();
+        self.scorecard.reset();
+        self.tee_up();
+    }
+    /// Strokes taken so far on the hole being played.
+    pub fn strokes(&self) -> u32 {
+        self.scorecard
+            .hole(self.course.current_index())
+            .map_or(0, |hole| hole.strokes)
+    }
+    /// Hands over every event since the last call.
+    pub fn take_events(&mut self) -> Vec<GameEvent> {
+        std::mem::take(&mut self.events)
+    }
+    fn emit(&mut self, event: GameEvent) {
+        self.events.push(event);
+    }
+    fn tee_up(&mut self) {
+        let tee = self.course.current().tee_point();
+        self.ball.reset_at(tee);
+        self.map = self.course.map().clone();
+        self.obstacles = self.map.obstacles.clone();
+        self.on_switch = false;
+        self.camera = view_of(&self.map, &self.ball);
+        self.last_rest = self.ball.exact_center();
+        self.over_cup = false;
+        self.state = GolfState::Aiming;
+    }
     fn update_aiming(&mut self, controls: &Controls) {
         match controls.aiming {
             Direction::Left => {
@@ -54,87 +212,253 @@ impl GameState {
         }
     }
     fn update_hitting(&mut self, controls: &Controls) {
-        if controls.hit == true && self.state == GolfState::Aiming {
+        if controls.hit && self.state == GolfState::Aiming {
             self.state = GolfState::Hitting;
         }
     }
+    fn update_camera(&mut self, controls: &Controls) {
+        match self.state {
+            GolfState::Aiming => {
+                let pan = Vec2::new(controls.pan.x as f64, controls.pan.y as f64);
+                self.camera.pan(pan.scale(PAN_SPEED * PHYSICS_DT));
+            }
+            GolfState::Rolling => self.camera.follow(self.ball.exact_center()),
+            _ => {}
+        }
+    }
     fn update_controls(&mut self, controls: &Controls) {
         self.update_aiming(controls);
         self.update_power_level(controls);
         self.update_hitting(controls);
     }
     pub fn update(&mut self, controls: &Controls) {
+        for obstacle in self.obstacles.iter_mut() {
+            obstacle.advance(PHYSICS_DT);
+        }
         self.update_state();
+        self.update_camera(controls);
         self.update_controls(controls);
     }
     pub fn update_state(&mut self) {
         match self.state {
-            GolfState::Aiming => {}
+            GolfState::Aiming => {
+                let knocks = self.ball.knock(&self.map, &self.obstacles);
+                if !knocks.is_empty() {
+                    self.state = GolfState::Rolling;
+                }
+                for normal in knocks {
+                    self.emit(GameEvent::Bounced { normal });
+                }
+            }
             GolfState::Rolling => {
-                self.ball.roll(&self.map);
-                let ball_center = self.ball.center();
-                let x_pos = (ball_center.x as i32) >> 4;
-                let y_pos = (ball_center.y as i32) >> 4;
-
-                let is_ball_velocity_in_range = f64::abs(self.ball.velocity.x()) <= 10.0
-                    && f64::abs(self.ball.velocity.y()) <= 10.0;
-                let is_ball_in_hole = self
-                    .map
-                    .tile_grid
-                    .tile_at(x_pos as usize, y_pos as usize)
-                    .get_type()
-                    == &TileType::Hole
-                    && is_ball_velocity_in_range;
-                if is_ball_in_hole {
+                let bounces = self.ball.roll(&self.map, &self.obstacles);
+                for normal in bounces {
+                    self.emit(GameEvent::Bounced { normal });
+                }
+                self.update_portals();
+                self.update_switches();
+                if self.update_hazards() {
+                    return;
+                }
+                if self.update_cup() {
+                    self.ball.velocity.stop();
                     self.state = GolfState::InHole;
-                    let reset_point = Point::new(28, 30);
-                    self.ball.reset_at(reset_point);
+                    self.emit(GameEvent::Sunk {
+                        hole: self.course.current_index(),
+                        strokes: self.strokes(),
+                    });
+                    return;
                 }
-                if self.ball.velocity.x() == 0.0 || self.ball.velocity.y() == 0.0 {
+                if self.ball.is_stopped() {
                     self.state = GolfState::Aiming;
+                    self.emit(GameEvent::Stopped {
+                        at: self.ball.exact_center(),
+                    });
                 }
             }
             GolfState::Hitting => {
+                let hole = self.course.current_index();
+                self.last_rest = self.ball.exact_center();
                 self.ball.hit();
+                self.scorecard.add_stroke(hole);
                 self.state = GolfState::Rolling;
+                self.emit(GameEvent::Hit {
+                    hole,
+                    stroke: self.strokes(),
+                });
             }
             GolfState::Stop => {
                 self.state = GolfState::Aiming;
             }
             GolfState::InHole => {
-                self.ball.loc_x(32);
-                self.ball.loc_y(48);
-                self.state = GolfState::Aiming;
+                self.scorecard.complete(self.course.current_index());
+                if self.course.advance() {
+                    self.tee_up();
+                    self.emit(GameEvent::HoleStarted {
+                        hole: self.course.current_index(),
+                    });
+                } else {
+                    self.state = GolfState::Finished;
+                    self.emit(GameEvent::CourseFinished);
+                }
             }
+            GolfState::Finished => {}
+        }
+    }
+    /// Puts a ball that was lost to a hazard back where it was last hit and
+    /// adds a penalty stroke. Returns whether the ball was lost.
+    fn update_hazards(&mut self) -> bool {
+        let ball_center = self.ball.center();
+        let tile = *self
+            .map()
+            .tile_grid
+            .tile_at(ball_center.x >> 4, ball_center.y >> 4)
+            .get_type();
+        let at = self.ball.exact_center();
+        let hazard = if tile.is_hazard() {
+            Hazard::Tile(tile)
+        } else if self.map().wells.iter().any(|well| well.swallows(at)) {
+            Hazard::BlackHole
+        } else {
+            return false;
+        };
+        self.scorecard.add_penalty(self.course.current_index());
+        self.ball.place(self.last_rest);
+        self.state = GolfState::Aiming;
+        self.emit(GameEvent::BallLost {
+            hazard,
+            returned_to: self.last_rest,
+        });
+        true
+    }
+    /// Decides what a ball rolling onto a cup does. Slow balls drop in, and
+    /// the closer to the middle they cross it the faster they may be. A ball
+    /// too fast to drop lips out, turned away from the cup by the rim the more
+    /// it clips the edge. Returns whether the ball dropped.
+    fn update_cup(&mut self) -> bool {
+        let ball_center = self.ball.center();
+        let (x, y) = (ball_center.x >> 4, ball_center.y >> 4);
+        if self.map().tile_grid.tile_at(x, y).get_type() != &TileType::Hole {
+            self.over_cup = false;
+            return false;
+        }
+        let cup = Vec2::new((x * 16 + 8) as f64, (y * 16 + 8) as f64);
+        let at = self.ball.exact_center();
+        let from_cup = at - cup;
+        if from_cup.length() > CUP_RADIUS {
+            self.over_cup = false;
+            return false;
+        }
+        if self.over_cup {
+            return false;
+        }
+        self.over_cup = true;
+
+        let velocity = self.ball.velocity.vec2();
+        let speed = velocity.length();
+        if speed == 0.0 {
+            return true;
+        }
+        let direction = velocity.scale(1.0 / speed);
+        // Part of the offset from the cup across the ball's line of travel.
+        let along = from_cup.x * direction.x + from_cup.y * direction.y;
+        let across = from_cup - direction.scale(along);
+        let edge = (across.length() / CUP_RADIUS).min(1.0);
+        let capture_speed =
+            CENTER_CAPTURE_SPEED + (EDGE_CAPTURE_SPEED - CENTER_CAPTURE_SPEED) * edge;
+        if speed <= capture_speed {
+            return true;
+        }
+
+        let turn = RIM_TURN * edge;
+        let outward = if across.length() > 0.0 {
+            across.scale(1.0 / across.length())
+        } else {
+            Vec2::new(0.0, 0.0)
+        };
+        let turned = direction.scale(turn.cos()) + outward.scale(turn.sin());
+        self.ball.velocity.update(turned.scale(speed * RIM_KEEP));
+        self.emit(GameEvent::LippedOut { at });
+        false
+    }
+    /// Flips a switch the ball has just rolled onto. Staying on it, or
+    /// rolling on to another switch tile next to it, does not flip it again.
+    fn update_switches(&mut self) {
+        let ball_center = self.ball.center();
+        let (column, row) = (ball_center.x >> 4, ball_center.y >> 4);
+        let TileType::Switch { id } = *self.map.tile_grid.tile_at(column, row).get_type() else {
+            self.on_switch = false;
+            return;
+        };
+        if self.on_switch {
+            return;
         }
+        self.on_switch = true;
+        self.map.flip_switch(id);
+        self.emit(GameEvent::Switched { column, row });
     }
-    pub fn draw(&self, frame: &mut [u8]) {
+    fn update_portals(&mut self) {
+        let ball_center = self.ball.center();
+        let x_pos = ball_center.x >> 4;
+        let y_pos = ball_center.y >> 4;
+        let on_portal = self.map().tile_grid.tile_at(x_pos, y_pos).get_type() == &TileType::Portal;
+        if !on_portal {
+            self.ball.portal_cooldown = self.ball.portal_cooldown.saturating_sub(1);
+            return;
+        }
+        if self.ball.portal_cooldown > 0 {
+            return;
+        }
+        if let Some(exit) = self.map.portals.exit_for(x_pos, y_pos) {
+            let velocity = exit.exit_velocity(self.ball.velocity.vec2());
+            let from = self.ball.exact_center();
+            self.ball.teleport(exit.center(), velocity);
+            self.emit(GameEvent::Teleported {
+                from,
+                to: self.ball.exact_center(),
+            });
+        }
+    }
+    /// Draws the game `alpha` of the way between the last two physics updates.
+    pub fn draw(&self, frame: &mut [u8], alpha: f64) {
+        let camera = &self.camera;
+        if camera.bounds.x < camera.size.x || camera.bounds.y < camera.size.y {
+            // The hole does not cover the view, clear what is around it.
+            background(frame);
+        }
+        self.map().draw(frame, camera);
+        for obstacle in self.obstacles.iter() {
+            obstacle.draw(frame, camera);
+        }
         match self.state {
             GolfState::Aiming => {
-                self.map.draw(frame);
-                self.ball.draw(frame);
-                let path = self.ball.aim_path();
-                path.draw(frame);
-            }
-            GolfState::Hitting => {
-                self.map.draw(frame);
-                self.ball.draw(frame);
+                let path = self
+                    .ball
+                    .aim_path(self.map(), &self.obstacles, &self.preview);
+                path.draw(frame, camera);
+                self.ball.draw(frame, camera);
             }
             GolfState::Rolling => {
-                self.map.draw(frame);
-                self.ball.draw(frame);
+                self.ball.draw_interpolated(frame, camera, alpha);
             }
-            GolfState::Stop => {
-                self.map.draw(frame);
-                self.ball.draw(frame);
-            }
-            GolfState::InHole => {
-                self.map.draw(frame);
-                self.ball.draw(frame);
+            GolfState::Hitting | GolfState::Stop | GolfState::InHole | GolfState::Finished => {
+                self.ball.draw(frame, camera);
             }
         }
     }
     pub fn ball_ref(&self) -> Rc<&Ball> {
-        return Rc::new(&self.ball);
+        Rc::new(&self.ball)
     }
 }
+
+/// A camera filling the screen above the information strip, centered on
+/// `ball` as far as `map` allows.
+fn view_of(map: &GameMap, ball: &Ball) -> Camera {
+    let size = map.size();
+    let mut camera = Camera::new(
+        Vec2::new(WIDTH as f64, VIEW_HEIGHT as f64),
+        Vec2::new(size.x as f64, size.y as f64),
+    );
+    camera.look_at(ball.exact_center());
+    camera
+}
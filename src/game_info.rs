@@ -1,10 +1,118 @@
-use crate::entities::ball::*;
+//! The information strip below the hole: which hole it is, its par, the
+//! strokes taken, and the power and angle of the next shot.
+use crate::common::*;
+use crate::game::*;
+use crate::geo::*;
+use crate::render::helpers::*;
+use crate::render::shapes::*;
+use crate::render::text::*;
+
+const PANEL_COLOR: [u8; 4] = [0x20, 0x20, 0x28, 0xff];
+const BORDER_COLOR: [u8; 4] = [0x60, 0x60, 0x70, 0xff];
+const TEXT_COLOR: [u8; 4] = [0xff, 0xff, 0xff, 0xff];
+const POWER_COLOR: [u8; 4] = [0xff, 0xc4, 0x00, 0xff];
+/// Top of the text, centered down the strip.
+const TEXT_TOP: i64 = VIEW_HEIGHT as i64 + (HEIGHT - VIEW_HEIGHT) as i64 / 2 - GLYPH_SIZE / 2;
+/// Where the power bar is drawn, outline included.
+pub const POWER_BAR: Rect = Rect {
+    p1: Vec2 {
+        x: 344,
+        y: TEXT_TOP - 2,
+    },
+    p2: Vec2 {
+        x: 504,
+        y: TEXT_TOP + GLYPH_SIZE + 2,
+    },
+};
+
+/// What the information strip shows, read from the game each update.
+#[derive(Clone, Debug, PartialEq)]
 pub struct InfoScreen {
-    ball: Ball,
+    /// 1-based number of the hole being played.
+    pub hole: usize,
+    pub holes: usize,
+    pub par: u32,
+    pub strokes: u32,
+    /// How far power is between its least and most, from 0 to 1.
+    pub power: f64,
+    /// Aim direction in whole degrees counterclockwise from pointing right.
+    pub angle: u32,
+}
+
+impl Default for InfoScreen {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl InfoScreen {
     pub fn new() -> Self {
-        Self { ball: Ball::new() }
+        Self {
+            hole: 1,
+            holes: 1,
+            par: 0,
+            strokes: 0,
+            power: 0.0,
+            angle: 0,
+        }
+    }
+
+    /// The information for `game` as it is now.
+    pub fn from_game(game: &GameState) -> Self {
+        let mut info = Self::new();
+        info.update(game);
+        info
+    }
+
+    pub fn update(&mut self, game: &GameState) {
+        self.hole = game.course.hole_number();
+        self.holes = game.course.hole_count();
+        self.par = game.course.current().par;
+        self.strokes = game.strokes();
+        self.power = game.ball.power.fraction();
+        self.angle = game.ball.angle.degrees().round() as u32 % 360;
+    }
+
+    /// Draws the strip over everything below the view of the hole.
+    pub fn draw(&self, frame: &mut [u8]) {
+        let screen = Rect::screen();
+        let strip = Rect::new(
+            Vec2::new(0, VIEW_HEIGHT as i64),
+            Vec2::new(WIDTH as i64, HEIGHT as i64),
+        );
+        fill_rect(frame, &screen, &strip, PANEL_COLOR);
+        line(
+            frame,
+            &screen,
+            (0, strip.p1.y),
+            (strip.p2.x - 1, strip.p1.y),
+            BORDER_COLOR,
+        );
+
+        let labels = [
+            (8, 112, format!("HOLE {}/{}", self.hole, self.holes)),
+            (112, 184, format!("PAR {}", self.par)),
+            (184, 296, format!("STROKES {}", self.strokes)),
+            (296, POWER_BAR.p1.x, "POWER".to_string()),
+        ];
+        for (left, right, text) in labels {
+            let area = Rect::new(Vec2::new(left, TEXT_TOP), Vec2::new(right, strip.p2.y));
+            draw_text(frame, &area, &text, TEXT_COLOR, Align::Left);
+        }
+        let angle = Rect::new(
+            Vec2::new(POWER_BAR.p2.x, TEXT_TOP),
+            Vec2::new(WIDTH as i64 - 8, strip.p2.y),
+        );
+        let text = format!("ANGLE {}", self.angle);
+        draw_text(frame, &angle, &text, TEXT_COLOR, Align::Right);
+
+        rect(frame, &screen, &POWER_BAR, BORDER_COLOR);
+        let inside = Rect::new(
+            POWER_BAR.p1 + Vec2::new(1, 1),
+            POWER_BAR.p2 - Vec2::new(1, 1),
+        );
+        let filled = ((inside.p2.x - inside.p1.x) as f64 * self.power).round() as i64;
+        let bar = Rect::new(inside.p1, Vec2::new(inside.p1.x + filled, inside.p2.y));
+        fill_rect(frame, &screen, &bar, POWER_COLOR);
     }
 }
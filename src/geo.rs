@@ -1,4 +1,4 @@
-#[derive(Copy, Clone, Debug, Default)]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
 pub struct Vec2<T> {
     pub x: T,
     pub y: T,
@@ -19,6 +19,14 @@ impl<T: core::ops::Add<Output = T>> core::ops::Add for Vec2<T> {
     }
 }
 
+impl<T: core::ops::Sub<Output = T>> core::ops::Sub for Vec2<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
 impl<T: core::ops::Mul<Output = T>> core::ops::Mul for Vec2<T> {
     type Output = Self;
 
@@ -27,6 +35,19 @@ impl<T: core::ops::Mul<Output = T>> core::ops::Mul for Vec2<T> {
     }
 }
 
+impl Vec2<f64> {
+    /// Multiplies both components by `by`.
+    #[inline(always)]
+    pub fn scale(self, by: f64) -> Self {
+        Self::new(self.x * by, self.y * by)
+    }
+
+    #[inline(always)]
+    pub fn length(self) -> f64 {
+        (self.x * self.x + self.y * self.y).sqrt()
+    }
+}
+
 /// A tiny position vector.
 #[derive(Copy, Clone, Debug, Default)]
 pub struct Point {
@@ -1,9 +1,14 @@
+pub mod camera;
 pub mod common;
 pub mod controls;
+pub mod course;
 pub mod entities;
 pub mod game;
 pub mod game_info;
 pub mod geo;
 pub mod map;
+pub mod physics;
 pub mod render;
+pub mod scoring;
 pub mod screens;
+pub mod sim;
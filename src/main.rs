@@ -2,25 +2,33 @@
 #![forbid(unsafe_code)]
 
 use paga::common::*;
-use paga::screens::game_screen::*;
+use paga::course::*;
+use paga::map::level::*;
+use paga::screens::scenes::*;
 
 use error_iter::ErrorIter as _;
 use game_loop::{game_loop, Time, TimeTrait as _};
 use log::error;
 use pixels::{Error, Pixels, SurfaceTexture};
 use std::{env, time::Duration};
-use winit::{
-    dpi::LogicalSize, event::VirtualKeyCode, event_loop::EventLoop, window::WindowBuilder,
-};
+use winit::{dpi::LogicalSize, event_loop::EventLoop, window::WindowBuilder};
+use winit_input_helper::WinitInputHelper;
 
+/// Upper limit on frames drawn per second. Physics runs at `PHYSICS_HZ`
+/// regardless.
 pub const FPS: usize = 144;
 pub const TIME_STEP: Duration = Duration::from_nanos(1_000_000_000 / FPS as u64);
-// Internally, the game advances at 60 fps
-// const ONE_FRAME: Duration = Duration::from_nanos(1_000_000_000 / 60);
 
 // const BOX_SIZE: i16 = 64;
 // const static [u8] BALL = [0xff,0xff];
 
+/// Everything the game loop runs on.
+struct App {
+    pixels: Pixels,
+    input: WinitInputHelper,
+    scenes: SceneManager,
+}
+
 fn log_error<E: std::error::Error + 'static>(method_name: &str, err: E) {
     error!("{method_name}() failed: {err}");
     for source in err.sources().skip(1) {
@@ -38,6 +46,28 @@ fn main() -> Result<(), Error> {
         .parse()
         .unwrap_or(false);
 
+    // Play a single level file with `LEVEL=path/to/level.txt`, or a folder
+    // of them in file name order with `COURSE=path/to/course`
+    let course = if let Ok(path) = env::var("LEVEL") {
+        match Level::load(path) {
+            Ok(level) => Course::new(vec![level]),
+            Err(err) => {
+                log_error("Level::load", err);
+                return Ok(());
+            }
+        }
+    } else if let Ok(dir) = env::var("COURSE") {
+        match Course::load_dir(dir) {
+            Ok(course) => course,
+            Err(err) => {
+                log_error("Course::load_dir", err);
+                return Ok(());
+            }
+        }
+    } else {
+        Course::standard()
+    };
+
     let window = {
         let size = LogicalSize::new(WIDTH as f64, HEIGHT as f64);
         let scaled_size = LogicalSize::new(WIDTH as f64 * 3.0, HEIGHT as f64 * 3.0);
@@ -55,23 +85,26 @@ fn main() -> Result<(), Error> {
         Pixels::new(WIDTH as u32, HEIGHT as u32, surface_texture)?
     };
 
-    let game = GameScreen::new(pixels, debug);
+    let app = App {
+        pixels,
+        input: WinitInputHelper::new(),
+        scenes: SceneManager::new(course, debug),
+    };
 
     game_loop(
         event_loop,
         window,
-        game,
-        FPS as u32,
+        app,
+        PHYSICS_HZ,
         0.1,
         move |g| {
             // Update the world
-            if !g.game.paused {
-                g.game.game.update(&g.game.controls);
-            }
+            g.game.scenes.update();
         },
         move |g| {
             // Drawing
-            g.game.game.draw(g.game.pixels.frame_mut());
+            let alpha = g.blending_factor();
+            g.game.scenes.draw(g.game.pixels.frame_mut(), alpha);
             if let Err(err) = g.game.pixels.render() {
                 log_error("pixels.render", err);
                 g.exit();
@@ -87,22 +120,15 @@ fn main() -> Result<(), Error> {
         |g, event| {
             // Let winit_input_helper collect events to build its state.
             if g.game.input.update(event) {
-                // Update controls
-                g.game.update_controls();
+                // Menus and controls
+                g.game.scenes.read_input(&g.game.input);
 
                 // Close events
-                if g.game.input.key_pressed(VirtualKeyCode::Escape)
-                    || g.game.input.close_requested()
-                {
+                if g.game.scenes.should_quit() || g.game.input.close_requested() {
                     g.exit();
                     return;
                 }
 
-                // Reset game
-                if g.game.input.key_pressed(VirtualKeyCode::R) {
-                    g.game.reset_game();
-                }
-
                 // Resize the window
                 if let Some(size) = g.game.input.window_resized() {
                     if let Err(err) = g.game.pixels.resize_surface(size.width, size.height) {
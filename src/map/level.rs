@@ -0,0 +1,1041 @@
+//! Plain-text level files.
+//!
+//! A level is a header of `key value` lines, a `---` separator and then the
+//! tile grid, one character per tile:
+//!
+//! ```text
+//! ; Lines starting with ';' are comments.
+//! name Hole 1
+//! size 40 23
+//! tee 20 3
+//! par 4
+//! portal 2,6 2,13:down
+//! ---
+//! ########################################
+//! #.@....................................#
+//! ...
+//! ```
+//!
+//! `size` is the grid width and height in tiles, `tee` the column and row the
+//! ball starts on and `par` the expected number of strokes. A hole can be any
+//! size, one larger than the 40 by 23 tiles that fit on screen scrolls to
+//! follow the ball. Each `portal`
+//! line links a group of `@` tiles by `column,row`, optionally followed by
+//! `:up`, `:down`, `:left` or `:right` to set the direction the ball leaves
+//! that portal in. Every portal tile has to be linked.
+//!
+//! `friction` and `restitution` lines change how every tile of one kind
+//! plays, for example `friction . 0.5` for rough ground or `restitution # 1.2`
+//! for bouncy walls. Friction is the fraction of speed a ball rolling on the
+//! tile keeps each second, from 0 to 1. Restitution is the fraction of speed
+//! kept bouncing off it, from 0 to 2.
+//!
+//! `planet` and `blackhole` lines place a gravity well as
+//! `column,row mass radius`, for example `planet 20,11 400 1.5`. The well sits
+//! on the center of that tile, decimals put it between tiles. `mass` is the
+//! pull in pixels per second squared one tile away, up to 100000, and `radius`
+//! the size of the body in tiles, up to 10. A black hole loses any ball that
+//! rolls inside its radius.
+//!
+//! Tiles are `.` ground, `#` wall, `*` bumper, `@` portal, `O` hole, `:`
+//! sand, `~` water and `x` void. Walls cut along a diagonal are `7`, `9`, `1` and `3`, and
+//! walls with a rounded inside corner `u`, `i`, `j` and `k`, each solid on the
+//! corner matching its place on the number pad or keyboard: `7` and `u` top
+//! left, `9` and `i` top right, `1` and `j` bottom left, `3` and `k` bottom
+//! right. `U`, `I`, `J` and `K` round off an outside corner instead, a quarter
+//! disc of wall on the same corner as their lower case letter, so `KJ` over
+//! `IU` is a round pillar.
+//!
+//! Slopes have no fixed character, each level gives its own with a `slope`
+//! line: `slope > right 2` makes `>` a slope running down to the right at
+//! strength 2. Slopes run `up`, `down`, `left`, `right`, `up-left`,
+//! `up-right`, `down-left` or `down-right`, at a strength from 1 to 3. Steeper
+//! slopes are shaded in narrower bands and a ball can only come to rest on
+//! the gentlest.
+//!
+//! Boosters and conveyors are given their characters the same way.
+//! `booster ^ up` makes `^` a booster that speeds the ball up by the same
+//! amount towards the top of the screen each time it rolls onto a patch of
+//! them. `conveyor = right 2` makes `=` a belt carrying the ball to the right
+//! at speed 2, from 1 to 3. Boosters and conveyors run `up`, `down`, `left` or
+//! `right`.
+//!
+//! Puzzle tiles are given their characters the same way. `gate G closed` and
+//! `gate g open` make `G` and `g` gates that start closed and open. A closed
+//! gate is a wall, an open one is ground. `switch S G g` makes `S` a switch
+//! that opens every closed `G` and `g` gate and closes every open one each time
+//! the ball rolls onto it. Gates have to be declared before the switches that
+//! move them. `oneway > right` makes `>` a wall the ball only gets through
+//! rolling to the right, and one-way walls also run `up`, `down` or `left`.
+//! Gates go back to how they started when the hole is restarted.
+//!
+//! Declare any tile given its character by the level before using it in
+//! `friction` or `restitution` lines.
+//!
+//! Moving obstacles are placed on the center of a tile like gravity wells:
+//! `block column,row width height` is a solid box, `bar column,row length` a
+//! thin bar and `windmill column,row blades length` that many bars of
+//! `length` spread around a hub. Sizes are in tiles, up to 20, and a windmill
+//! has from 1 to 8 blades. Each `key seconds column,row degrees` line after
+//! one of them adds a keyframe to its track: the obstacle is moved by
+//! `column,row` tiles, which may be negative but have to leave the
+//! obstacle's position on the map, and turned clockwise by
+//! `degrees` that many seconds in. Keyframes go in time order, the obstacle
+//! moves steadily between them and the track starts over from the first after
+//! the last, so `key 0 0,0 0` and `key 4 0,0 360` turn a windmill once every
+//! four seconds. An obstacle without keyframes stands still. A ball hit by a
+//! moving obstacle is carried along with it.
+//!
+//! A ball that rolls onto water or void is lost and played again from where
+//! it was last hit, one penalty stroke added.
+use crate::entities::gravity::*;
+use crate::entities::obstacles::*;
+use crate::entities::portals::*;
+use crate::entities::tiles::*;
+use crate::geo::*;
+use crate::map::map::*;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// The first hole, shipped with the game.
+pub const MAP1: &str = include_str!("../../assets/maps/map1.txt");
+
+/// Everything a level file describes.
+#[derive(Clone, Debug)]
+pub struct Level {
+    pub name: String,
+    pub par: u32,
+    /// Column and row of the tile the ball starts on.
+    pub tee: Point,
+    pub map: GameMap,
+}
+
+/// What went wrong while parsing a level.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseErrorKind {
+    UnknownKey(String),
+    MissingValue(&'static str),
+    UnexpectedValue(String),
+    InvalidNumber(String),
+    OutOfRange { value: f64, min: f64, max: f64 },
+    InvalidCoordinate(String),
+    InvalidFacing(String),
+    DuplicateKey(&'static str),
+    MissingKey(&'static str),
+    MissingGrid,
+    UnknownTile(char),
+    SymbolInUse(char),
+    InvalidDownhill(String),
+    NotAGate(char),
+    KeyframeWithoutObstacle,
+    KeyframeOutOfOrder,
+    RowLength { expected: usize, found: usize },
+    RowCount { expected: usize, found: usize },
+    OutOfBounds { column: usize, row: usize },
+    TeeOnWall,
+    TeeInHazard,
+    NotAPortal { column: usize, row: usize },
+    PortalAlreadyLinked { column: usize, row: usize },
+    PortalGroupTooSmall,
+    UnlinkedPortal,
+}
+
+/// A parse error, pointing at the 1-based line and column it was found at.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+/// Errors from reading a level file off disk.
+#[derive(Debug)]
+pub enum LevelError {
+    Io(std::io::Error),
+    Parse(ParseError),
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseErrorKind::UnknownKey(key) => write!(f, "unknown header key `{key}`"),
+            ParseErrorKind::MissingValue(what) => write!(f, "expected {what}"),
+            ParseErrorKind::UnexpectedValue(value) => write!(f, "unexpected value `{value}`"),
+            ParseErrorKind::InvalidNumber(value) => write!(f, "`{value}` is not a number"),
+            ParseErrorKind::OutOfRange { value, min, max } => {
+                write!(f, "{value} is not between {min} and {max}")
+            }
+            ParseErrorKind::InvalidCoordinate(value) => {
+                write!(f, "`{value}` is not a `column,row` coordinate")
+            }
+            ParseErrorKind::InvalidFacing(value) => {
+                write!(f, "`{value}` is not a facing, use up, down, left or right")
+            }
+            ParseErrorKind::DuplicateKey(key) => write!(f, "`{key}` is set more than once"),
+            ParseErrorKind::MissingKey(key) => write!(f, "the header has no `{key}` line"),
+            ParseErrorKind::MissingGrid => write!(f, "expected a `---` line before the tiles"),
+            ParseErrorKind::UnknownTile(symbol) => write!(f, "unknown tile `{symbol}`"),
+            ParseErrorKind::SymbolInUse(symbol) => {
+                write!(f, "`{symbol}` is already used for another tile")
+            }
+            ParseErrorKind::InvalidDownhill(value) => {
+                write!(f, "`{value}` is not a slope direction")
+            }
+            ParseErrorKind::NotAGate(symbol) => write!(f, "`{symbol}` is not a gate"),
+            ParseErrorKind::KeyframeWithoutObstacle => {
+                write!(f, "`key` lines have to follow an obstacle")
+            }
+            ParseErrorKind::KeyframeOutOfOrder => {
+                write!(f, "keyframes have to be later than the one before")
+            }
+            ParseErrorKind::RowLength { expected, found } => {
+                write!(f, "row is {found} tiles wide, expected {expected}")
+            }
+            ParseErrorKind::RowCount { expected, found } => {
+                write!(f, "grid has {found} rows, expected {expected}")
+            }
+            ParseErrorKind::OutOfBounds { column, row } => {
+                write!(f, "{column},{row} is outside the grid")
+            }
+            ParseErrorKind::TeeOnWall => write!(f, "the tee is on a wall"),
+            ParseErrorKind::TeeInHazard => write!(f, "the tee is in a hazard"),
+            ParseErrorKind::NotAPortal { column, row } => {
+                write!(f, "tile {column},{row} is not a portal")
+            }
+            ParseErrorKind::PortalAlreadyLinked { column, row } => {
+                write!(f, "portal {column},{row} is already linked")
+            }
+            ParseErrorKind::PortalGroupTooSmall => {
+                write!(f, "a portal group needs at least two portals")
+            }
+            ParseErrorKind::UnlinkedPortal => write!(f, "portal is not linked to any other"),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.kind
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl fmt::Display for LevelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LevelError::Io(err) => write!(f, "could not read level: {err}"),
+            LevelError::Parse(err) => write!(f, "could not parse level: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for LevelError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LevelError::Io(err) => Some(err),
+            LevelError::Parse(err) => Some(err),
+        }
+    }
+}
+
+impl From<std::io::Error> for LevelError {
+    fn from(err: std::io::Error) -> Self {
+        LevelError::Io(err)
+    }
+}
+
+impl From<ParseError> for LevelError {
+    fn from(err: ParseError) -> Self {
+        LevelError::Parse(err)
+    }
+}
+
+impl Level {
+    /// Parses a level from the text format described in the module docs.
+    pub fn parse(source: &str) -> Result<Level, ParseError> {
+        Parser::default().parse(source)
+    }
+
+    /// Reads and parses a level file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Level, LevelError> {
+        let source = fs::read_to_string(path)?;
+        Ok(Level::parse(&source)?)
+    }
+
+    /// The first hole, shipped with the game.
+    pub fn map1() -> Level {
+        Level::parse(MAP1).expect("bundled map1 should parse")
+    }
+
+    /// Pixel position of the ball's top left corner when it sits on the tee.
+    pub fn tee_point(&self) -> Point {
+        Point::new(self.tee.x * 16 + 4, self.tee.y * 16 + 4)
+    }
+}
+
+impl FromStr for Level {
+    type Err = ParseError;
+
+    fn from_str(source: &str) -> Result<Level, ParseError> {
+        Level::parse(source)
+    }
+}
+
+/// A whitespace separated word and the 1-based column it starts at.
+type Token<'a> = (usize, &'a str);
+
+/// A moving obstacle as written in the header, with the line and column of
+/// its position and of each keyframe's offset.
+struct ObstacleRef {
+    line: usize,
+    column: usize,
+    obstacle: Obstacle,
+    keys: Vec<(usize, usize)>,
+}
+
+/// A portal as written in the header, with where it was written.
+struct PortalRef {
+    line: usize,
+    column: usize,
+    portal: Portal,
+}
+
+#[derive(Default)]
+struct Parser {
+    name: Option<String>,
+    size: Option<(usize, usize)>,
+    tee: Option<(usize, usize, usize, usize)>,
+    par: Option<u32>,
+    portal_groups: Vec<Vec<PortalRef>>,
+    /// Friction and restitution set for a kind of tile.
+    surfaces: Vec<(TileType, Option<f64>, Option<f64>)>,
+    /// Tiles given their character by the level.
+    legend: Vec<(char, TileType)>,
+    /// Gravity wells with the line and column their position was written at.
+    wells: Vec<(usize, usize, GravityWell)>,
+    /// The gate ids each switch toggles, indexed by switch id.
+    switches: Vec<Vec<u8>>,
+    obstacles: Vec<ObstacleRef>,
+}
+
+/// Largest `mass` a gravity well can have.
+const MAX_WELL_MASS: f64 = 100000.0;
+/// Largest `radius` of a gravity well, in tiles.
+const MAX_WELL_RADIUS: f64 = 10.0;
+/// Largest size of an obstacle, in tiles.
+const MAX_OBSTACLE_SIZE: f64 = 20.0;
+/// Most blades a windmill can have.
+const MAX_BLADES: u32 = 8;
+
+fn error(line: usize, column: usize, kind: ParseErrorKind) -> ParseError {
+    ParseError { line, column, kind }
+}
+
+fn tokens(line: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut start: Option<usize> = None;
+    for (i, c) in line.char_indices() {
+        match (c.is_whitespace(), start) {
+            (true, Some(s)) => {
+                tokens.push((s, &line[s..i]));
+                start = None;
+            }
+            (false, None) => start = Some(i),
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        tokens.push((s, &line[s..]));
+    }
+    tokens
+        .into_iter()
+        .map(|(offset, word)| (line[..offset].chars().count() + 1, word))
+        .collect()
+}
+
+fn parse_number<T: FromStr>(line: usize, token: Token) -> Result<T, ParseError> {
+    token
+        .1
+        .parse()
+        .map_err(|_| error(line, token.0, ParseErrorKind::InvalidNumber(token.1.into())))
+}
+
+fn parse_downhill(line: usize, token: Token) -> Result<Downhill, ParseError> {
+    match token.1 {
+        "up" => Ok(Downhill::Up),
+        "up-right" => Ok(Downhill::UpRight),
+        "right" => Ok(Downhill::Right),
+        "down-right" => Ok(Downhill::DownRight),
+        "down" => Ok(Downhill::Down),
+        "down-left" => Ok(Downhill::DownLeft),
+        "left" => Ok(Downhill::Left),
+        "up-left" => Ok(Downhill::UpLeft),
+        word => Err(error(
+            line,
+            token.0,
+            ParseErrorKind::InvalidDownhill(word.into()),
+        )),
+    }
+}
+
+fn parse_facing(line: usize, column: usize, word: &str) -> Result<Facing, ParseError> {
+    match word {
+        "up" => Ok(Facing::Up),
+        "down" => Ok(Facing::Down),
+        "left" => Ok(Facing::Left),
+        "right" => Ok(Facing::Right),
+        _ => Err(error(
+            line,
+            column,
+            ParseErrorKind::InvalidFacing(word.into()),
+        )),
+    }
+}
+
+fn parse_portal(line: usize, token: Token) -> Result<PortalRef, ParseError> {
+    let (column, word) = token;
+    let invalid = || error(line, column, ParseErrorKind::InvalidCoordinate(word.into()));
+    let (coordinate, facing) = match word.split_once(':') {
+        Some((coordinate, facing)) => {
+            let facing_column = column + coordinate.chars().count() + 1;
+            (coordinate, Some(parse_facing(line, facing_column, facing)?))
+        }
+        None => (word, None),
+    };
+    let (x, y) = coordinate.split_once(',').ok_or_else(invalid)?;
+    let x: usize = x.parse().map_err(|_| invalid())?;
+    let y: usize = y.parse().map_err(|_| invalid())?;
+    Ok(PortalRef {
+        line,
+        column,
+        portal: Portal::new(x, y, facing),
+    })
+}
+
+/// Parses a `column,row` position that may fall between tiles.
+fn parse_position(line: usize, token: Token) -> Result<(f64, f64), ParseError> {
+    let (column, word) = token;
+    let invalid = || error(line, column, ParseErrorKind::InvalidCoordinate(word.into()));
+    let (x, y) = word.split_once(',').ok_or_else(invalid)?;
+    let x: f64 = x.parse().map_err(|_| invalid())?;
+    let y: f64 = y.parse().map_err(|_| invalid())?;
+    if !(x >= 0.0 && y >= 0.0) {
+        return Err(invalid());
+    }
+    Ok((x, y))
+}
+
+/// Parses a `column,row` move in tiles, either of which may be negative.
+fn parse_offset(line: usize, token: Token) -> Result<Vec2<f64>, ParseError> {
+    let (column, word) = token;
+    let invalid = || error(line, column, ParseErrorKind::InvalidCoordinate(word.into()));
+    let (x, y) = word.split_once(',').ok_or_else(invalid)?;
+    let x: f64 = x.parse().map_err(|_| invalid())?;
+    let y: f64 = y.parse().map_err(|_| invalid())?;
+    if !(x.is_finite() && y.is_finite()) {
+        return Err(invalid());
+    }
+    Ok(Vec2::new(x, y))
+}
+
+/// Parses a number and checks it is between `min` and `max`.
+fn parse_in_range(line: usize, token: Token, min: f64, max: f64) -> Result<f64, ParseError> {
+    let value: f64 = parse_number(line, token)?;
+    if !(min..=max).contains(&value) {
+        return Err(error(
+            line,
+            token.0,
+            ParseErrorKind::OutOfRange { value, min, max },
+        ));
+    }
+    Ok(value)
+}
+
+/// Parses a whole number from 1 to `max`, such as a slope's strength.
+fn parse_step(line: usize, token: Token, max: u8) -> Result<u8, ParseError> {
+    let step: u8 = parse_number(line, token)?;
+    if !(1..=max).contains(&step) {
+        return Err(error(
+            line,
+            token.0,
+            ParseErrorKind::OutOfRange {
+                value: step as f64,
+                min: 1.0,
+                max: max as f64,
+            },
+        ));
+    }
+    Ok(step)
+}
+
+impl Parser {
+    fn parse(mut self, source: &str) -> Result<Level, ParseError> {
+        let mut lines = source.lines().enumerate().map(|(i, l)| (i + 1, l));
+        let mut last_line = 0;
+        let mut found_grid = false;
+        for (number, line) in lines.by_ref() {
+            last_line = number;
+            let trimmed = line.trim();
+            if trimmed == "---" {
+                found_grid = true;
+                break;
+            }
+            if trimmed.is_empty() || trimmed.starts_with(';') {
+                continue;
+            }
+            self.header_line(number, line)?;
+        }
+        if !found_grid {
+            return Err(error(last_line + 1, 1, ParseErrorKind::MissingGrid));
+        }
+        let grid_start = last_line + 1;
+
+        let missing = |key| error(grid_start - 1, 1, ParseErrorKind::MissingKey(key));
+        let (width, height) = self.size.ok_or_else(|| missing("size"))?;
+        let (tee_line, tee_column, tee_x, tee_y) = self.tee.ok_or_else(|| missing("tee"))?;
+        let par = self.par.ok_or_else(|| missing("par"))?;
+
+        let mut rows: Vec<Vec<TileType>> = Vec::with_capacity(height);
+        let mut trailing_blank: Option<usize> = None;
+        for (number, line) in lines {
+            let line = line.trim_end();
+            if line.is_empty() {
+                trailing_blank.get_or_insert(number);
+                continue;
+            }
+            if let Some(blank) = trailing_blank {
+                return Err(error(
+                    blank,
+                    1,
+                    ParseErrorKind::RowLength {
+                        expected: width,
+                        found: 0,
+                    },
+                ));
+            }
+            if rows.len() == height {
+                return Err(error(
+                    number,
+                    1,
+                    ParseErrorKind::RowCount {
+                        expected: height,
+                        found: rows.len() + 1,
+                    },
+                ));
+            }
+            let mut row = Vec::with_capacity(width);
+            for (column, symbol) in line.chars().enumerate() {
+                let tile_type = self.tile_for(symbol).ok_or_else(|| {
+                    error(number, column + 1, ParseErrorKind::UnknownTile(symbol))
+                })?;
+                row.push(tile_type);
+            }
+            if row.len() != width {
+                let column = row.len().min(width) + 1;
+                return Err(error(
+                    number,
+                    column,
+                    ParseErrorKind::RowLength {
+                        expected: width,
+                        found: row.len(),
+                    },
+                ));
+            }
+            rows.push(row);
+        }
+        if rows.len() != height {
+            let line = trailing_blank.unwrap_or(grid_start + rows.len());
+            return Err(error(
+                line,
+                1,
+                ParseErrorKind::RowCount {
+                    expected: height,
+                    found: rows.len(),
+                },
+            ));
+        }
+
+        if tee_x >= width || tee_y >= height {
+            return Err(error(
+                tee_line,
+                tee_column,
+                ParseErrorKind::OutOfBounds {
+                    column: tee_x,
+                    row: tee_y,
+                },
+            ));
+        }
+        if rows[tee_y][tee_x].is_solid() {
+            return Err(error(tee_line, tee_column, ParseErrorKind::TeeOnWall));
+        }
+        if rows[tee_y][tee_x].is_hazard() {
+            return Err(error(tee_line, tee_column, ParseErrorKind::TeeInHazard));
+        }
+
+        let portals = self.link_portals(&rows, grid_start)?;
+        let mut tile_grid = TileGrid::from_rows(&rows);
+        for (tile_type, friction, restitution) in self.surfaces.iter() {
+            tile_grid.configure(*tile_type, *friction, *restitution);
+        }
+        let mut map = GameMap::from_parts(tile_grid, portals);
+        map.switches = std::mem::take(&mut self.switches);
+        for (line, column, well) in self.wells.iter() {
+            let x = (well.center.x / 16.0) as usize;
+            let y = (well.center.y / 16.0) as usize;
+            if x >= width || y >= height {
+                return Err(error(
+                    *line,
+                    *column,
+                    ParseErrorKind::OutOfBounds { column: x, row: y },
+                ));
+            }
+            map.wells.push(*well);
+        }
+        for ObstacleRef {
+            line,
+            column,
+            obstacle,
+            keys,
+        } in self.obstacles.iter()
+        {
+            let x = (obstacle.anchor.x / 16.0) as usize;
+            let y = (obstacle.anchor.y / 16.0) as usize;
+            if x >= width || y >= height {
+                return Err(error(
+                    *line,
+                    *column,
+                    ParseErrorKind::OutOfBounds { column: x, row: y },
+                ));
+            }
+            // Every keyframe has to keep the obstacle's anchor on the map.
+            let anchor = obstacle.anchor.scale(1.0 / 16.0);
+            for (keyframe, (key_line, key_column)) in obstacle.track.keyframes().iter().zip(keys) {
+                let offset = keyframe.offset.scale(1.0 / 16.0);
+                for (value, from, size) in
+                    [(offset.x, anchor.x, width), (offset.y, anchor.y, height)]
+                {
+                    let (min, max) = (-from, size as f64 - from);
+                    if !(min..max).contains(&value) {
+                        return Err(error(
+                            *key_line,
+                            *key_column,
+                            ParseErrorKind::OutOfRange { value, min, max },
+                        ));
+                    }
+                }
+            }
+            map.obstacles.push(obstacle.clone());
+        }
+        Ok(Level {
+            name: self.name.unwrap_or_default(),
+            par,
+            tee: Point::new(tee_x, tee_y),
+            map,
+        })
+    }
+
+    fn header_line(&mut self, number: usize, line: &str) -> Result<(), ParseError> {
+        let tokens = tokens(line);
+        let (key_column, key) = tokens[0];
+        let values = &tokens[1..];
+        let end_column = line.trim_end().chars().count() + 1;
+        let value = |index: usize, what| {
+            values
+                .get(index)
+                .copied()
+                .ok_or_else(|| error(number, end_column, ParseErrorKind::MissingValue(what)))
+        };
+        let no_more = |count: usize| match values.get(count) {
+            Some((column, word)) => Err(error(
+                number,
+                *column,
+                ParseErrorKind::UnexpectedValue((*word).into()),
+            )),
+            None => Ok(()),
+        };
+        let duplicate = |key| error(number, key_column, ParseErrorKind::DuplicateKey(key));
+
+        match key {
+            "name" => {
+                if self.name.is_some() {
+                    return Err(duplicate("name"));
+                }
+                let (column, _) = value(0, "a name")?;
+                let offset = line
+                    .char_indices()
+                    .nth(column - 1)
+                    .map_or(line.len(), |(i, _)| i);
+                self.name = Some(line[offset..].trim_end().to_string());
+            }
+            "size" => {
+                if self.size.is_some() {
+                    return Err(duplicate("size"));
+                }
+                let width = parse_number(number, value(0, "a width")?)?;
+                let height = parse_number(number, value(1, "a height")?)?;
+                no_more(2)?;
+                self.size = Some((width, height));
+            }
+            "tee" => {
+                if self.tee.is_some() {
+                    return Err(duplicate("tee"));
+                }
+                let column_token = value(0, "a column")?;
+                let x = parse_number(number, column_token)?;
+                let y = parse_number(number, value(1, "a row")?)?;
+                no_more(2)?;
+                self.tee = Some((number, column_token.0, x, y));
+            }
+            "par" => {
+                if self.par.is_some() {
+                    return Err(duplicate("par"));
+                }
+                let par = parse_number(number, value(0, "a par")?)?;
+                no_more(1)?;
+                self.par = Some(par);
+            }
+            "friction" | "restitution" => {
+                let (column, symbol) = value(0, "a tile")?;
+                let mut chars = symbol.chars();
+                let tile_type = match (chars.next(), chars.next()) {
+                    (Some(c), None) => self.tile_for(c),
+                    _ => None,
+                }
+                .ok_or_else(|| {
+                    error(
+                        number,
+                        column,
+                        ParseErrorKind::UnknownTile(symbol.chars().next().unwrap_or(' ')),
+                    )
+                })?;
+                let amount_token = value(1, "a number")?;
+                let amount: f64 = parse_number(number, amount_token)?;
+                no_more(2)?;
+                let max = if key == "friction" { 1.0 } else { 2.0 };
+                if !(0.0..=max).contains(&amount) {
+                    return Err(error(
+                        number,
+                        amount_token.0,
+                        ParseErrorKind::OutOfRange {
+                            value: amount,
+                            min: 0.0,
+                            max,
+                        },
+                    ));
+                }
+                let surface = if key == "friction" {
+                    (tile_type, Some(amount), None)
+                } else {
+                    (tile_type, None, Some(amount))
+                };
+                self.surfaces.push(surface);
+            }
+            "slope" => {
+                let symbol = self.new_symbol(number, value(0, "a character")?)?;
+                let downhill = parse_downhill(number, value(1, "a direction")?)?;
+                let strength = parse_step(number, value(2, "a strength")?, MAX_SLOPE_STRENGTH)?;
+                no_more(3)?;
+                self.legend
+                    .push((symbol, TileType::Slope { downhill, strength }));
+            }
+            "booster" | "conveyor" => {
+                let symbol = self.new_symbol(number, value(0, "a character")?)?;
+                let (column, word) = value(1, "a direction")?;
+                let facing = parse_facing(number, column, word)?;
+                let tile_type = if key == "booster" {
+                    no_more(2)?;
+                    TileType::Booster { facing }
+                } else {
+                    let speed = parse_step(number, value(2, "a speed")?, MAX_CONVEYOR_SPEED)?;
+                    no_more(3)?;
+                    TileType::Conveyor { facing, speed }
+                };
+                self.legend.push((symbol, tile_type));
+            }
+            "gate" => {
+                let symbol = self.new_symbol(number, value(0, "a character")?)?;
+                let (column, state) = value(1, "`open` or `closed`")?;
+                let open = match state {
+                    "open" => true,
+                    "closed" => false,
+                    _ => {
+                        return Err(error(
+                            number,
+                            column,
+                            ParseErrorKind::UnexpectedValue(state.into()),
+                        ))
+                    }
+                };
+                no_more(2)?;
+                let id = self.count(|tile_type| matches!(tile_type, TileType::Gate { .. }));
+                let id = self.next_id(number, key_column, id)?;
+                self.legend.push((symbol, TileType::Gate { id, open }));
+            }
+            "switch" => {
+                let symbol = self.new_symbol(number, value(0, "a character")?)?;
+                value(1, "a gate")?;
+                let mut gates = Vec::new();
+                for (column, word) in values[1..].iter() {
+                    for (offset, gate) in word.chars().enumerate() {
+                        match self.tile_for(gate) {
+                            Some(TileType::Gate { id, .. }) => gates.push(id),
+                            _ => {
+                                return Err(error(
+                                    number,
+                                    column + offset,
+                                    ParseErrorKind::NotAGate(gate),
+                                ))
+                            }
+                        }
+                    }
+                }
+                let id = self.next_id(number, key_column, self.switches.len())?;
+                self.switches.push(gates);
+                self.legend.push((symbol, TileType::Switch { id }));
+            }
+            "oneway" => {
+                let symbol = self.new_symbol(number, value(0, "a character")?)?;
+                let (column, word) = value(1, "a direction")?;
+                let facing = parse_facing(number, column, word)?;
+                no_more(2)?;
+                self.legend.push((symbol, TileType::OneWay { facing }));
+            }
+            "portal" => {
+                if values.len() < 2 {
+                    return Err(error(
+                        number,
+                        key_column,
+                        ParseErrorKind::PortalGroupTooSmall,
+                    ));
+                }
+                let group = values
+                    .iter()
+                    .map(|token| parse_portal(number, *token))
+                    .collect::<Result<Vec<_>, _>>()?;
+                self.portal_groups.push(group);
+            }
+            "planet" | "blackhole" => {
+                let kind = if key == "planet" {
+                    WellKind::Planet
+                } else {
+                    WellKind::BlackHole
+                };
+                let position_token = value(0, "a `column,row` position")?;
+                let (x, y) = parse_position(number, position_token)?;
+                let mass = parse_in_range(number, value(1, "a mass")?, 0.0, MAX_WELL_MASS)?;
+                let radius = parse_in_range(number, value(2, "a radius")?, 0.0, MAX_WELL_RADIUS)?;
+                no_more(3)?;
+                let center = Vec2::new(x * 16.0 + 8.0, y * 16.0 + 8.0);
+                let well = GravityWell::new(kind, center, mass, radius * 16.0);
+                self.wells.push((number, position_token.0, well));
+            }
+            "block" | "bar" | "windmill" => {
+                let position_token = value(0, "a `column,row` position")?;
+                let (x, y) = parse_position(number, position_token)?;
+                let size = |index, what| {
+                    parse_in_range(number, value(index, what)?, 0.0, MAX_OBSTACLE_SIZE)
+                        .map(|tiles| tiles * 16.0)
+                };
+                let kind = match key {
+                    "block" => {
+                        let width = size(1, "a width")?;
+                        let height = size(2, "a height")?;
+                        no_more(3)?;
+                        ObstacleKind::Block { width, height }
+                    }
+                    "bar" => {
+                        let length = size(1, "a length")?;
+                        no_more(2)?;
+                        ObstacleKind::Bar { length }
+                    }
+                    _ => {
+                        let blades_token = value(1, "a number of blades")?;
+                        let blades: u32 = parse_number(number, blades_token)?;
+                        if !(1..=MAX_BLADES).contains(&blades) {
+                            return Err(error(
+                                number,
+                                blades_token.0,
+                                ParseErrorKind::OutOfRange {
+                                    value: blades as f64,
+                                    min: 1.0,
+                                    max: MAX_BLADES as f64,
+                                },
+                            ));
+                        }
+                        let length = size(2, "a length")?;
+                        no_more(3)?;
+                        ObstacleKind::Windmill { blades, length }
+                    }
+                };
+                let anchor = Vec2::new(x * 16.0 + 8.0, y * 16.0 + 8.0);
+                let obstacle = Obstacle::new(kind, anchor, Track::default());
+                self.obstacles.push(ObstacleRef {
+                    line: number,
+                    column: position_token.0,
+                    obstacle,
+                    keys: Vec::new(),
+                });
+            }
+            "key" => {
+                let time_token = value(0, "a time in seconds")?;
+                let time: f64 = parse_number(number, time_token)?;
+                let offset_token = value(1, "a `column,row` offset")?;
+                let offset = parse_offset(number, offset_token)?;
+                let degrees_token = value(2, "an angle in degrees")?;
+                let degrees: f64 = parse_number(number, degrees_token)?;
+                if !degrees.is_finite() {
+                    return Err(error(
+                        number,
+                        degrees_token.0,
+                        ParseErrorKind::InvalidNumber(degrees_token.1.into()),
+                    ));
+                }
+                no_more(3)?;
+                let Some(ObstacleRef { obstacle, keys, .. }) = self.obstacles.last_mut() else {
+                    return Err(error(
+                        number,
+                        key_column,
+                        ParseErrorKind::KeyframeWithoutObstacle,
+                    ));
+                };
+                let after = obstacle.track.keyframes().last().map_or(0.0, |k| k.time);
+                let first = obstacle.track.keyframes().is_empty();
+                if !time.is_finite() || time < after || (!first && time == after) {
+                    return Err(error(
+                        number,
+                        time_token.0,
+                        ParseErrorKind::KeyframeOutOfOrder,
+                    ));
+                }
+                keys.push((number, offset_token.0));
+                obstacle.track.push(Keyframe {
+                    time,
+                    offset: offset.scale(16.0),
+                    angle: degrees.to_radians(),
+                });
+            }
+            _ => {
+                return Err(error(
+                    number,
+                    key_column,
+                    ParseErrorKind::UnknownKey(key.into()),
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads the character a line such as `slope` or `gate` gives its tile,
+    /// which no other tile may already use.
+    fn new_symbol(&self, number: usize, token: Token) -> Result<char, ParseError> {
+        let (column, word) = token;
+        let mut chars = word.chars();
+        let symbol = match (chars.next(), chars.next()) {
+            (Some(c), None) => c,
+            _ => {
+                return Err(error(
+                    number,
+                    column,
+                    ParseErrorKind::UnexpectedValue(word.into()),
+                ))
+            }
+        };
+        if self.tile_for(symbol).is_some() {
+            return Err(error(number, column, ParseErrorKind::SymbolInUse(symbol)));
+        }
+        Ok(symbol)
+    }
+
+    /// How many tiles given a character by the level match `kind`.
+    fn count(&self, kind: impl Fn(&TileType) -> bool) -> usize {
+        self.legend
+            .iter()
+            .filter(|(_, tile_type)| kind(tile_type))
+            .count()
+    }
+
+    /// Checks there is room for one more gate or switch, numbered `id`.
+    fn next_id(&self, number: usize, column: usize, id: usize) -> Result<u8, ParseError> {
+        u8::try_from(id).map_err(|_| {
+            error(
+                number,
+                column,
+                ParseErrorKind::OutOfRange {
+                    value: id as f64 + 1.0,
+                    min: 1.0,
+                    max: u8::MAX as f64 + 1.0,
+                },
+            )
+        })
+    }
+
+    /// The tile a grid character stands for in this level.
+    fn tile_for(&self, symbol: char) -> Option<TileType> {
+        self.legend
+            .iter()
+            .find(|(known, _)| *known == symbol)
+            .map(|(_, tile_type)| *tile_type)
+            .or_else(|| TileType::from_symbol(symbol))
+    }
+
+    fn link_portals(
+        &mut self,
+        rows: &[Vec<TileType>],
+        grid_start: usize,
+    ) -> Result<PortalNetwork, ParseError> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+        let mut linked = vec![vec![false; width]; height];
+        let mut network = PortalNetwork::new();
+        for group in self.portal_groups.drain(..) {
+            let mut portals = Vec::with_capacity(group.len());
+            for PortalRef {
+                line,
+                column,
+                portal,
+            } in group
+            {
+                let (x, y) = (portal.tile.x, portal.tile.y);
+                if x >= width || y >= height {
+                    return Err(error(
+                        line,
+                        column,
+                        ParseErrorKind::OutOfBounds { column: x, row: y },
+                    ));
+                }
+                if rows[y][x] != TileType::Portal {
+                    return Err(error(
+                        line,
+                        column,
+                        ParseErrorKind::NotAPortal { column: x, row: y },
+                    ));
+                }
+                if linked[y][x] {
+                    return Err(error(
+                        line,
+                        column,
+                        ParseErrorKind::PortalAlreadyLinked { column: x, row: y },
+                    ));
+                }
+                linked[y][x] = true;
+                portals.push(portal);
+            }
+            network.link(PortalGroup::new(portals));
+        }
+        for (y, row) in rows.iter().enumerate() {
+            for (x, tile_type) in row.iter().enumerate() {
+                if *tile_type == TileType::Portal && !linked[y][x] {
+                    return Err(error(grid_start + y, x + 1, ParseErrorKind::UnlinkedPortal));
+                }
+            }
+        }
+        Ok(network)
+    }
+}
@@ -1,25 +1,64 @@
+use crate::camera::*;
+use crate::entities::gravity::*;
+use crate::entities::obstacles::*;
+use crate::entities::portals::*;
 use crate::entities::tiles::*;
 use crate::geo::*;
+use crate::map::level::*;
 
+#[derive(Clone, Debug)]
 pub struct GameMap {
     pub point: Point,
     pub width: usize,
     pub height: usize,
     pub tile_grid: TileGrid,
+    pub portals: PortalNetwork,
+    /// Planets and black holes pulling on the ball.
+    pub wells: Vec<GravityWell>,
+    /// Moving obstacles at the start of their tracks.
+    pub obstacles: Vec<Obstacle>,
+    /// The gate ids each switch toggles, indexed by switch id.
+    pub switches: Vec<Vec<u8>>,
 }
+impl Default for GameMap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl GameMap {
     pub fn new() -> GameMap {
-        let point = Point::new(0, 0);
+        Level::map1().map
+    }
+
+    /// Builds a map sized to fit `tile_grid`.
+    pub fn from_parts(tile_grid: TileGrid, portals: PortalNetwork) -> GameMap {
         GameMap {
-            point: point,
-            width: 640,
-            height: 368,
-            tile_grid: TileGrid::new(),
+            point: Point::new(0, 0),
+            width: tile_grid.xsize() * 16,
+            height: tile_grid.ysize() * 16,
+            tile_grid,
+            portals,
+            wells: Vec::new(),
+            obstacles: Vec::new(),
+            switches: Vec::new(),
         }
     }
 
-    pub fn draw(&self, frame: &mut [u8]) {
-        self.tile_grid.draw(frame);
+    /// Toggles every gate linked to switch `id`.
+    pub fn flip_switch(&mut self, id: u8) {
+        if let Some(gates) = self.switches.get(id as usize) {
+            for gate in gates.iter() {
+                self.tile_grid.toggle_gates(*gate);
+            }
+        }
+    }
+
+    pub fn draw(&self, frame: &mut [u8], camera: &Camera) {
+        self.tile_grid.draw(frame, camera);
+        for well in self.wells.iter() {
+            well.draw(frame, camera);
+        }
     }
     pub fn point(&self) -> Vec2<usize> {
         Vec2::new(self.point.x, self.point.y)
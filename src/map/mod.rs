@@ -1 +1,3 @@
+pub mod level;
+#[allow(clippy::module_inception)]
 pub mod map;
@@ -0,0 +1,487 @@
+//! Swept collision of the ball against the `TileGrid`.
+//!
+//! Instead of checking a few points after a full step, the ball is swept as a
+//! circle along its whole motion and the earliest wall it touches is found.
+//! The ball stops there, bounces off the surface normal where it touched, and
+//! carries on with whatever motion it had left, so no speed is fast enough to
+//! pass through a wall. Hitting the corner of a tile turns the ball by the
+//! angle it met the corner at, rather than flipping it straight back.
+use crate::entities::tiles::*;
+use crate::geo::*;
+
+/// Pixel size of a tile edge.
+pub const TILE_PIXELS: f64 = 16.0;
+/// Most bounces worked out in a single step. Anything left after that is
+/// dropped, which only happens when the ball is wedged into a corner.
+pub const MAX_BOUNCES: usize = 4;
+/// Fastest a ball leaves a bounce, in pixels per second. Bumpers, and walls a
+/// level gives a restitution over 1, speed the ball up on every hit, so a
+/// ball caught between them would otherwise never stop speeding up.
+pub const MAX_BALL_SPEED: f64 = 3000.0;
+/// How far the ball is kept off a wall after touching it, so rounding never
+/// leaves it inside.
+pub(crate) const SKIN: f64 = 1e-6;
+
+/// Where a moving box first touches a solid tile.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Contact {
+    /// Fraction of the motion covered before touching, from 0 to 1.
+    pub time: f64,
+    /// Unit normal of the surface that was hit, pointing away from it.
+    pub normal: Vec2<f64>,
+    /// Column and row of the tile that was hit. May be off the grid.
+    pub tile: (isize, isize),
+}
+
+/// A box that was moved through the grid for one step.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sweep {
+    pub center: Vec2<f64>,
+    pub velocity: Vec2<f64>,
+    /// Every wall touched, in order. `time` is relative to the whole step.
+    pub contacts: Vec<Contact>,
+}
+
+/// Reflects `velocity` off a surface with unit normal `normal`. Only the
+/// speed into the surface is scaled by `restitution`, so a ball glancing off
+/// a wall keeps most of its speed.
+pub fn reflect(velocity: Vec2<f64>, normal: Vec2<f64>, restitution: f64) -> Vec2<f64> {
+    let along = velocity.x * normal.x + velocity.y * normal.y;
+    velocity - normal.scale((1.0 + restitution) * along)
+}
+
+/// Slows `velocity` down to `max` pixels per second if it is any faster,
+/// keeping its direction.
+pub fn limit_speed(velocity: Vec2<f64>, max: f64) -> Vec2<f64> {
+    let speed = velocity.length();
+    if speed > max {
+        velocity.scale(max / speed)
+    } else {
+        velocity
+    }
+}
+
+/// Finds the first solid tile hit by a circle of `radius` centered on
+/// `center` moving by `motion`. Circles already touching a wall and moving
+/// along or away from it do not collide with it. One-way walls are skipped
+/// by circles moving their way or already partway through them.
+pub fn sweep_circle(
+    grid: &TileGrid,
+    center: Vec2<f64>,
+    radius: f64,
+    motion: Vec2<f64>,
+) -> Option<Contact> {
+    let min_x = center.x.min(center.x + motion.x) - radius;
+    let max_x = center.x.max(center.x + motion.x) + radius;
+    let min_y = center.y.min(center.y + motion.y) - radius;
+    let max_y = center.y.max(center.y + motion.y) + radius;
+    let first_column = (min_x / TILE_PIXELS).floor() as isize;
+    let last_column = (max_x / TILE_PIXELS).floor() as isize;
+    let first_row = (min_y / TILE_PIXELS).floor() as isize;
+    let last_row = (max_y / TILE_PIXELS).floor() as isize;
+
+    let mut first: Option<Contact> = None;
+    for row in first_row..=last_row {
+        for column in first_column..=last_column {
+            let shape = grid.shape_at(column, row);
+            if shape == TileShape::Empty {
+                continue;
+            }
+            let origin = Vec2::new(column as f64, row as f64).scale(TILE_PIXELS);
+            if let Some(facing) = grid.one_way_at(column, row) {
+                // Passed moving the right way, and never pushed out of partway.
+                let through = dot(motion, facing.unit()) > 0.0;
+                if through || touches_square(center, radius, origin) {
+                    continue;
+                }
+            }
+            let hit = sweep_shape(shape, origin, center, motion, radius);
+            if let Some((time, normal)) = hit {
+                if first.is_none_or(|c| time < c.time) {
+                    first = Some(Contact {
+                        time,
+                        normal,
+                        tile: (column, row),
+                    });
+                }
+            }
+        }
+    }
+    first
+}
+
+/// Moves a circle with `velocity` for `dt` seconds, bouncing off walls on the
+/// way. Each bounce loses speed according to the restitution of the tile hit.
+pub fn move_circle(
+    grid: &TileGrid,
+    center: Vec2<f64>,
+    radius: f64,
+    velocity: Vec2<f64>,
+    dt: f64,
+) -> Sweep {
+    let mut center = center;
+    let mut velocity = velocity;
+    let mut contacts = Vec::new();
+    let mut elapsed = 0.0;
+    for _ in 0..=MAX_BOUNCES {
+        let remaining = 1.0 - elapsed;
+        if remaining <= 0.0 {
+            break;
+        }
+        let motion = velocity.scale(dt * remaining);
+        match sweep_circle(grid, center, radius, motion) {
+            Some(contact) if contacts.len() < MAX_BOUNCES => {
+                center = center + motion.scale(contact.time) + contact.normal.scale(SKIN);
+                let (column, row) = contact.tile;
+                velocity = reflect(velocity, contact.normal, grid.restitution_at(column, row));
+                velocity = limit_speed(velocity, MAX_BALL_SPEED);
+                elapsed += remaining * contact.time;
+                contacts.push(Contact {
+                    time: elapsed,
+                    ..contact
+                });
+            }
+            Some(contact) => {
+                center = center + motion.scale(contact.time) + contact.normal.scale(SKIN);
+                break;
+            }
+            None => {
+                center = center + motion;
+                break;
+            }
+        }
+    }
+    Sweep {
+        center,
+        velocity,
+        contacts,
+    }
+}
+
+/// A piece of a tile's outline.
+#[derive(Copy, Clone, Debug)]
+enum Feature {
+    /// A straight side, with the normal pointing out of the tile.
+    Edge {
+        from: Vec2<f64>,
+        to: Vec2<f64>,
+        normal: Vec2<f64>,
+    },
+    /// A sharp corner.
+    Point(Vec2<f64>),
+    /// A hollow quarter circle of `radius` around `center`, on the side of it
+    /// `toward` points to.
+    Arc {
+        center: Vec2<f64>,
+        radius: f64,
+        toward: Vec2<f64>,
+    },
+    /// A solid quarter circle of `radius` around `center`, on the side of it
+    /// `toward` points to.
+    Bulge {
+        center: Vec2<f64>,
+        radius: f64,
+        toward: Vec2<f64>,
+    },
+}
+
+/// The outline of the solid part of a tile whose top left is at `origin`.
+fn features(shape: TileShape, origin: Vec2<f64>) -> Vec<Feature> {
+    let corner = |corner: Corner| origin + corner.offset(TILE_PIXELS);
+    match shape {
+        TileShape::Empty => Vec::new(),
+        TileShape::Full => polygon(&[
+            corner(Corner::TopLeft),
+            corner(Corner::TopRight),
+            corner(Corner::BottomRight),
+            corner(Corner::BottomLeft),
+        ]),
+        TileShape::Diagonal(solid) => {
+            let [a, b] = solid.neighbors();
+            polygon(&[corner(solid), corner(a), corner(b)])
+        }
+        TileShape::Curved(solid) => {
+            let center = corner(solid.opposite());
+            let mut features = Vec::new();
+            for side in solid.neighbors() {
+                let (from, to) = (corner(solid), corner(side));
+                // The straight sides face away from the hollow.
+                let normal = outward(from, to, center);
+                features.push(Feature::Edge { from, to, normal });
+                features.push(Feature::Point(to));
+            }
+            features.push(Feature::Point(corner(solid)));
+            features.push(Feature::Arc {
+                center,
+                radius: TILE_PIXELS,
+                toward: corner(solid) - center,
+            });
+            features
+        }
+        TileShape::Rounded(solid) => {
+            let center = corner(solid);
+            let middle = origin + Vec2::new(TILE_PIXELS / 2.0, TILE_PIXELS / 2.0);
+            let mut features = vec![Feature::Point(center)];
+            for side in solid.neighbors() {
+                let to = corner(side);
+                let normal = outward(center, to, middle);
+                features.push(Feature::Edge {
+                    from: center,
+                    to,
+                    normal,
+                });
+                features.push(Feature::Point(to));
+            }
+            features.push(Feature::Bulge {
+                center,
+                radius: TILE_PIXELS,
+                toward: corner(solid.opposite()) - center,
+            });
+            features
+        }
+    }
+}
+
+/// Edges and corners of a convex polygon.
+fn polygon(vertices: &[Vec2<f64>]) -> Vec<Feature> {
+    let count = vertices.len() as f64;
+    let centroid = vertices
+        .iter()
+        .fold(Vec2::new(0.0, 0.0), |sum, v| sum + *v)
+        .scale(1.0 / count);
+    let mut features = Vec::new();
+    for (i, from) in vertices.iter().enumerate() {
+        let to = vertices[(i + 1) % vertices.len()];
+        features.push(Feature::Edge {
+            from: *from,
+            to,
+            normal: outward(*from, to, centroid),
+        });
+        features.push(Feature::Point(*from));
+    }
+    features
+}
+
+/// Unit normal of the line from `from` to `to` pointing away from `inside`.
+fn outward(from: Vec2<f64>, to: Vec2<f64>, inside: Vec2<f64>) -> Vec2<f64> {
+    let along = to - from;
+    let normal = Vec2::new(along.y, -along.x).scale(1.0 / along.length());
+    if dot(normal, inside - from) > 0.0 {
+        normal.scale(-1.0)
+    } else {
+        normal
+    }
+}
+
+/// Sweeps a circle against the solid part of a tile whose top left is at
+/// `origin`. Returns the entry time and the normal at the point of contact.
+fn sweep_shape(
+    shape: TileShape,
+    origin: Vec2<f64>,
+    center: Vec2<f64>,
+    motion: Vec2<f64>,
+    radius: f64,
+) -> Option<(f64, Vec2<f64>)> {
+    let features = features(shape, origin);
+
+    // Already overlapping, only collide if moving further in.
+    if let Some(normal) = overlap(shape, origin, &features, center, radius) {
+        let approaching = dot(motion, normal) < 0.0;
+        return approaching.then_some((0.0, normal));
+    }
+
+    let mut first: Option<(f64, Vec2<f64>)> = None;
+    for feature in features.iter() {
+        let hit = match *feature {
+            Feature::Edge { from, to, normal } => {
+                ray_edge(center, motion, radius, from, to, normal)
+            }
+            Feature::Point(point) => ray_circle(center, motion, point, radius).map(|time| {
+                (
+                    time,
+                    (center + motion.scale(time) - point).scale(1.0 / radius),
+                )
+            }),
+            Feature::Arc {
+                center: hollow,
+                radius: size,
+                toward,
+            } => ray_arc(center, motion, radius, hollow, size, toward),
+            Feature::Bulge {
+                center: disc,
+                radius: size,
+                toward,
+            } => ray_circle(center, motion, disc, size + radius).and_then(|time| {
+                let at = center + motion.scale(time) - disc;
+                in_quarter(at, toward).then_some((time, at.scale(1.0 / at.length())))
+            }),
+        };
+        if let Some((time, normal)) = hit {
+            if first.is_none_or(|(first_time, _)| time < first_time) {
+                first = Some((time, normal));
+            }
+        }
+    }
+    first
+}
+
+/// The direction to push a circle out of a tile it overlaps, if it does.
+fn overlap(
+    shape: TileShape,
+    origin: Vec2<f64>,
+    features: &[Feature],
+    center: Vec2<f64>,
+    radius: f64,
+) -> Option<Vec2<f64>> {
+    let local = center - origin;
+    let inside = shape.contains(local.x, local.y, TILE_PIXELS);
+    let mut nearest: Option<(f64, Vec2<f64>)> = None;
+    for feature in features.iter() {
+        let (distance, normal) = match *feature {
+            Feature::Edge { from, to, normal } => {
+                let closest = closest_on_segment(center, from, to);
+                let distance = (center - closest).length();
+                if inside {
+                    // Depth below the side, only the shallowest one counts.
+                    (-dot(center - from, normal), normal)
+                } else if distance > 0.0 {
+                    (distance, (center - closest).scale(1.0 / distance))
+                } else {
+                    (0.0, normal)
+                }
+            }
+            Feature::Point(point) => {
+                if inside {
+                    continue;
+                }
+                let distance = (center - point).length();
+                if distance == 0.0 {
+                    continue;
+                }
+                (distance, (center - point).scale(1.0 / distance))
+            }
+            Feature::Arc {
+                center: hollow,
+                radius: size,
+                toward,
+            } => {
+                let from_hollow = center - hollow;
+                let distance = from_hollow.length();
+                if !in_quarter(from_hollow, toward) || distance == 0.0 {
+                    continue;
+                }
+                ((size - distance).abs(), from_hollow.scale(-1.0 / distance))
+            }
+            Feature::Bulge {
+                center: disc,
+                radius: size,
+                toward,
+            } => {
+                let from_disc = center - disc;
+                let distance = from_disc.length();
+                if !in_quarter(from_disc, toward) || distance == 0.0 {
+                    continue;
+                }
+                ((distance - size).abs(), from_disc.scale(1.0 / distance))
+            }
+        };
+        if nearest.is_none_or(|(nearest_distance, _)| distance < nearest_distance) {
+            nearest = Some((distance, normal));
+        }
+    }
+    let (distance, normal) = nearest?;
+    (inside || distance < radius).then_some(normal)
+}
+
+/// Time a circle moving by `motion` touches the straight side of a tile, and
+/// the side's normal.
+fn ray_edge(
+    center: Vec2<f64>,
+    motion: Vec2<f64>,
+    radius: f64,
+    from: Vec2<f64>,
+    to: Vec2<f64>,
+    normal: Vec2<f64>,
+) -> Option<(f64, Vec2<f64>)> {
+    let closing = dot(motion, normal);
+    if closing >= 0.0 {
+        return None;
+    }
+    let time = (radius - dot(center - from, normal)) / closing;
+    if !(0.0..=1.0).contains(&time) {
+        return None;
+    }
+    let hit = center + motion.scale(time);
+    let along = to - from;
+    let part = dot(hit - from, along) / dot(along, along);
+    (0.0..=1.0).contains(&part).then_some((time, normal))
+}
+
+/// Time a circle moving by `motion` inside a hollow quarter circle touches
+/// its rim, and the normal there, pointing back into the hollow.
+fn ray_arc(
+    center: Vec2<f64>,
+    motion: Vec2<f64>,
+    radius: f64,
+    hollow: Vec2<f64>,
+    size: f64,
+    toward: Vec2<f64>,
+) -> Option<(f64, Vec2<f64>)> {
+    let reach = size - radius;
+    let from_hollow = center - hollow;
+    let a = dot(motion, motion);
+    let b = dot(from_hollow, motion);
+    let c = dot(from_hollow, from_hollow) - reach * reach;
+    if a == 0.0 || c >= 0.0 {
+        return None;
+    }
+    // Starting inside the rim, so it is crossed on the way out.
+    let time = (-b + (b * b - a * c).sqrt()) / a;
+    if !(0.0..=1.0).contains(&time) {
+        return None;
+    }
+    let at = from_hollow + motion.scale(time);
+    in_quarter(at, toward).then_some((time, at.scale(-1.0 / at.length())))
+}
+
+/// Whether `offset` points into the same quarter as `toward`.
+fn in_quarter(offset: Vec2<f64>, toward: Vec2<f64>) -> bool {
+    offset.x * toward.x >= 0.0 && offset.y * toward.y >= 0.0
+}
+
+/// Whether a circle overlaps the whole tile whose top left is at `origin`.
+fn touches_square(center: Vec2<f64>, radius: f64, origin: Vec2<f64>) -> bool {
+    let closest = Vec2::new(
+        center.x.clamp(origin.x, origin.x + TILE_PIXELS),
+        center.y.clamp(origin.y, origin.y + TILE_PIXELS),
+    );
+    (center - closest).length() < radius
+}
+
+fn closest_on_segment(point: Vec2<f64>, from: Vec2<f64>, to: Vec2<f64>) -> Vec2<f64> {
+    let along = to - from;
+    let part = (dot(point - from, along) / dot(along, along)).clamp(0.0, 1.0);
+    from + along.scale(part)
+}
+
+/// Time a point moving by `motion` first crosses into a circle. Only
+/// touching the edge does not count.
+fn ray_circle(origin: Vec2<f64>, motion: Vec2<f64>, center: Vec2<f64>, radius: f64) -> Option<f64> {
+    let from_center = origin - center;
+    let a = dot(motion, motion);
+    if a == 0.0 {
+        return None;
+    }
+    let b = dot(from_center, motion);
+    let c = dot(from_center, from_center) - radius * radius;
+    let discriminant = b * b - a * c;
+    if discriminant <= 0.0 {
+        return None;
+    }
+    let time = (-b - discriminant.sqrt()) / a;
+    (0.0..=1.0).contains(&time).then_some(time)
+}
+
+fn dot(a: Vec2<f64>, b: Vec2<f64>) -> f64 {
+    a.x * b.x + a.y * b.y
+}
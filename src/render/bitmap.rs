@@ -0,0 +1,37 @@
+//! Monochrome bitmaps as the Atari stored them: 1 bit per pixel, 32 pixels
+//! to a word with the leftmost pixel in the top bit, each row starting on a
+//! new word.
+
+/// Pixels held in each word. Only the low 32 bits of wider words are read.
+pub const WORD_BITS: usize = 32;
+
+/// Expands a monochrome bitmap of `width` by `height` pixels into RGBA, set
+/// bits in `foreground` and clear bits in `background`. Pixels past the end
+/// of `words` are background.
+pub fn decode_mono<W>(
+    words: &[W],
+    width: usize,
+    height: usize,
+    foreground: [u8; 4],
+    background: [u8; 4],
+) -> Vec<u8>
+where
+    W: Copy + Into<u64>,
+{
+    let words_per_row = width.div_ceil(WORD_BITS);
+    let mut pixels = Vec::with_capacity(width * height * 4);
+    for y in 0..height {
+        for x in 0..width {
+            let word = words
+                .get(y * words_per_row + x / WORD_BITS)
+                .map_or(0, |&word| word.into());
+            let bit = WORD_BITS - 1 - x % WORD_BITS;
+            if word >> bit & 1 == 1 {
+                pixels.extend_from_slice(&foreground);
+            } else {
+                pixels.extend_from_slice(&background);
+            }
+        }
+    }
+    pixels
+}
@@ -1,4 +1,4 @@
-pub(crate) trait Drawable {
+pub trait Drawable {
     fn width(&self) -> usize;
     fn height(&self) -> usize;
     fn pixels(&self) -> &[u8];
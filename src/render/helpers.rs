@@ -1,11 +1,11 @@
 use crate::common::*;
 use crate::geo::*;
 use crate::render::drawing::*;
-use core::cmp::min;
-use line_drawing::Bresenham;
+use crate::render::shapes::*;
+use line_drawing::octant::Octant;
 
 pub(crate) fn background(frame: &mut [u8]) {
-    for (i, pixel) in frame.chunks_exact_mut(4).enumerate() {
+    for pixel in frame.chunks_exact_mut(4) {
         let bg_color = [0x48, 0xb2, 0xe8, 0xff];
         pixel.copy_from_slice(&bg_color);
     }
@@ -39,58 +39,281 @@ pub(crate) fn map_color_to_rgba(items: &[u8]) -> Vec<u8> {
                 ret_vec.push(0);
                 ret_vec.push(255);
             }
+            4 => {
+                ret_vec.push(222);
+                ret_vec.push(196);
+                ret_vec.push(120);
+                ret_vec.push(255);
+            }
+            5 => {
+                ret_vec.push(36);
+                ret_vec.push(92);
+                ret_vec.push(204);
+                ret_vec.push(255);
+            }
+            6 => {
+                ret_vec.push(20);
+                ret_vec.push(16);
+                ret_vec.push(28);
+                ret_vec.push(255);
+            }
+            7 => {
+                ret_vec.push(152);
+                ret_vec.push(152);
+                ret_vec.push(152);
+                ret_vec.push(255);
+            }
+            8 => {
+                ret_vec.push(104);
+                ret_vec.push(104);
+                ret_vec.push(104);
+                ret_vec.push(255);
+            }
+            9 => {
+                ret_vec.push(214);
+                ret_vec.push(40);
+                ret_vec.push(57);
+                ret_vec.push(255);
+            }
+            10 => {
+                ret_vec.push(255);
+                ret_vec.push(160);
+                ret_vec.push(160);
+                ret_vec.push(255);
+            }
+            11 => {
+                ret_vec.push(255);
+                ret_vec.push(196);
+                ret_vec.push(0);
+                ret_vec.push(255);
+            }
+            12 => {
+                ret_vec.push(58);
+                ret_vec.push(58);
+                ret_vec.push(70);
+                ret_vec.push(255);
+            }
+            13 => {
+                ret_vec.push(112);
+                ret_vec.push(112);
+                ret_vec.push(128);
+                ret_vec.push(255);
+            }
+            14 => {
+                ret_vec.push(230);
+                ret_vec.push(120);
+                ret_vec.push(20);
+                ret_vec.push(255);
+            }
+            15 => {
+                ret_vec.push(40);
+                ret_vec.push(200);
+                ret_vec.push(120);
+                ret_vec.push(255);
+            }
             _ => {}
         }
     }
-    return ret_vec;
+    ret_vec
+}
+
+/// The part of `clip` inside the frame, where drawing is allowed.
+fn drawable_area(clip: &Rect) -> Rect {
+    clip.intersection(&Rect::screen())
 }
 
-pub(crate) fn blit<S>(screen: &mut [u8], dest: &Point, sprite: &S)
+fn put_pixel(screen: &mut [u8], x: i64, y: i64, color: [u8; 4]) {
+    let i = (x as usize + y as usize * WIDTH) * 4;
+    screen[i..i + 4].copy_from_slice(&color);
+}
+
+/// Copies the non-transparent pixels of `sprite` with its top left at `dest`.
+/// Only pixels inside `clip` and the frame are drawn.
+pub fn blit<S>(screen: &mut [u8], clip: &Rect, dest: (i64, i64), sprite: &S)
 where
     S: Drawable,
 {
-    assert!(dest.x + sprite.width() <= WIDTH);
-    assert!(dest.y + sprite.height() <= HEIGHT);
+    let (dx, dy) = dest;
+    let area = Rect::from_drawable(Vec2::new(dx, dy), sprite).intersection(&drawable_area(clip));
+    if area.is_empty() {
+        return;
+    }
 
     let pixels = sprite.pixels();
-    let width = sprite.width() * 4;
-
-    let mut s = 0;
-    for y in 0..sprite.height() {
-        let i = dest.x * 4 + dest.y * WIDTH * 4 + y * WIDTH * 4;
+    let span = (area.p2.x - area.p1.x) as usize * 4;
+    for y in area.p1.y..area.p2.y {
+        let i = (area.p1.x as usize + y as usize * WIDTH) * 4;
+        let s = ((area.p1.x - dx) as usize + (y - dy) as usize * sprite.width()) * 4;
 
         // Merge pixels from sprite into screen
-        let zipped = screen[i..i + width].iter_mut().zip(&pixels[s..s + width]);
+        let zipped = screen[i..i + span].iter_mut().zip(&pixels[s..s + span]);
         for (left, &right) in zipped {
             if right > 0 {
                 *left = right;
             }
         }
+    }
+}
 
-        s += width;
+/// Draws a line from `p1` to `p2`, both ends included. Only pixels inside
+/// `clip` and the frame are drawn, so the line keeps its slope however far
+/// off screen its ends are.
+pub fn line(screen: &mut [u8], clip: &Rect, p1: (i64, i64), p2: (i64, i64), color: [u8; 4]) {
+    for (_, (x, y)) in clipped_line(p1, p2, &drawable_area(clip)) {
+        put_pixel(screen, x, y, color);
     }
 }
 
-pub(crate) fn line(screen: &mut [u8], p1: &Point, p2: &Point, color: [u8; 4]) {
-    let p1 = (p1.x as i64, p1.y as i64);
-    let p2 = (p2.x as i64, p2.y as i64);
-    for (x, y) in Bresenham::new(p1, p2) {
-        let x = min(x as usize, (WIDTH - 1) as usize);
-        let y = min(y as usize, (HEIGHT - 1) as usize);
+/// The pixels of the line from `p1` to `p2` that are inside `area`, each with
+/// its number of steps from `p1`. They are the pixels `Bresenham` gives for
+/// the whole line, but the steps outside `area` are jumped over rather than
+/// walked, so a line costs only what it draws however long it is.
+fn clipped_line(
+    p1: (i64, i64),
+    p2: (i64, i64),
+    area: &Rect,
+) -> impl Iterator<Item = (u128, (i64, i64))> {
+    let start = (p1.0 as i128, p1.1 as i128);
+    let end = (p2.0 as i128, p2.1 as i128);
+    // Turned into its octant, the line steps one pixel along x at a time and
+    // has climbed floor(step * rise / run) pixels along y.
+    let octant = Octant::new(start, end);
+    let (run, rise) = octant.to((end.0 - start.0, end.1 - start.1));
+    let (run, rise) = (run as u128, rise as u128);
+    let along = octant.from((1, 0));
+    let across = octant.from((0, 1));
+    let climbed = move |step: u128| (step * rise).checked_div(run).unwrap_or(0);
 
-        let i = x * 4 + y * WIDTH as usize * 4;
+    // Each axis of `area` keeps one run of steps, as both coordinates only
+    // ever move one way.
+    let (mut first, mut last) = (0, run as i128);
+    let axes = [
+        (start.0, along.0, across.0, area.p1.x, area.p2.x),
+        (start.1, along.1, across.1, area.p1.y, area.p2.y),
+    ];
+    for (from, along, across, low, high) in axes {
+        let (low, high) = (low as i128 - from, high as i128 - 1 - from);
+        if along != 0 {
+            let (low, high) = if along > 0 {
+                (low, high)
+            } else {
+                (-high, -low)
+            };
+            first = first.max(low);
+            last = last.min(high);
+            continue;
+        }
+        let (low, high) = if across > 0 {
+            (low, high)
+        } else {
+            (-high, -low)
+        };
+        let (low, high) = (low.max(0), high.min(rise as i128));
+        if low > high {
+            last = -1;
+        } else if rise > 0 {
+            let (low, high) = (low as u128, high as u128);
+            first = first.max((low * run).div_ceil(rise) as i128);
+            last = last.min((((high + 1) * run - 1) / rise) as i128);
+        }
+    }
+
+    (first.max(0)..=last).map(move |step| {
+        let (step, climb) = (step as u128, climbed(step as u128) as i128);
+        let x = start.0 + step as i128 * along.0 + climb * across.0;
+        let y = start.1 + step as i128 * along.1 + climb * across.1;
+        (step, (x as i64, y as i64))
+    })
+}
 
-        screen[i..i + 4].copy_from_slice(&color);
+/// Draws a dot every `spacing` pixels along the lines joining `points`.
+/// Only dots inside `clip` and the frame are drawn.
+pub fn dotted_line(
+    screen: &mut [u8],
+    clip: &Rect,
+    points: &[(i64, i64)],
+    spacing: usize,
+    color: [u8; 4],
+) {
+    let area = drawable_area(clip);
+    let spacing = spacing as u128;
+    // Steps taken along the lines before the current one.
+    let mut walked: u128 = 0;
+    for pair in points.windows(2) {
+        // Each segment starts where the last one ended, skip the shared pixel.
+        for (step, (x, y)) in clipped_line(pair[0], pair[1], &area) {
+            if step > 0 && (walked + step).is_multiple_of(spacing) {
+                put_pixel(screen, x, y, color);
+            }
+        }
+        let (dx, dy) = (pair[0].0.abs_diff(pair[1].0), pair[0].1.abs_diff(pair[1].1));
+        walked += dx.max(dy) as u128;
     }
 }
 
-pub(crate) fn rect(screen: &mut [u8], p1: &Point, p2: &Point, color: [u8; 4]) {
-    let p2 = Point::new(p2.x - 1, p2.y - 1);
-    let p3 = Point::new(p1.x, p2.y);
-    let p4 = Point::new(p2.x, p1.y);
+/// Fills a circle centered on `center`. Only pixels inside `clip` and the
+/// frame are drawn.
+pub fn circle(screen: &mut [u8], clip: &Rect, center: (i64, i64), radius: i64, color: [u8; 4]) {
+    if radius < 0 {
+        return;
+    }
+    let (cx, cy) = center;
+    let area = Rect::new(
+        Vec2::new(cx.saturating_sub(radius), cy.saturating_sub(radius)),
+        Vec2::new(
+            cx.saturating_add(radius).saturating_add(1),
+            cy.saturating_add(radius).saturating_add(1),
+        ),
+    )
+    .intersection(&drawable_area(clip));
+    for y in area.p1.y..area.p2.y {
+        for x in area.p1.x..area.p2.x {
+            let (dx, dy) = (x - cx, y - cy);
+            if dx * dx + dy * dy > radius * radius {
+                continue;
+            }
+            put_pixel(screen, x, y, color);
+        }
+    }
+}
+
+/// Fills `area` with `color`. Only pixels inside `clip` and the frame are
+/// drawn.
+pub fn fill_rect(screen: &mut [u8], clip: &Rect, area: &Rect, color: [u8; 4]) {
+    let area = area.intersection(&drawable_area(clip));
+    for y in area.p1.y..area.p2.y {
+        for x in area.p1.x..area.p2.x {
+            put_pixel(screen, x, y, color);
+        }
+    }
+}
+
+/// Darkens `area` to `keep` of its brightness, from 0 for black to 1 for
+/// unchanged. Only pixels inside `clip` and the frame are changed.
+pub fn dim(screen: &mut [u8], clip: &Rect, area: &Rect, keep: f64) {
+    let keep = keep.clamp(0.0, 1.0);
+    let area = area.intersection(&drawable_area(clip));
+    for y in area.p1.y..area.p2.y {
+        for x in area.p1.x..area.p2.x {
+            let i = (x as usize + y as usize * WIDTH) * 4;
+            for channel in &mut screen[i..i + 3] {
+                *channel = (*channel as f64 * keep).round() as u8;
+            }
+        }
+    }
+}
+
+/// Draws the outline of `outline`. Only pixels inside `clip` and the frame
+/// are drawn.
+pub fn rect(screen: &mut [u8], clip: &Rect, outline: &Rect, color: [u8; 4]) {
+    if outline.is_empty() {
+        return;
+    }
+    let (left, top) = (outline.p1.x, outline.p1.y);
+    let (right, bottom) = (outline.p2.x - 1, outline.p2.y - 1);
 
-    line(screen, p1, &p3, color);
-    line(screen, &p3, &p2, color);
-    line(screen, &p2, &p4, color);
-    line(screen, &p4, p1, color);
+    line(screen, clip, (left, top), (left, bottom), color);
+    line(screen, clip, (left, bottom), (right, bottom), color);
+    line(screen, clip, (right, bottom), (right, top), color);
+    line(screen, clip, (right, top), (left, top), color);
 }
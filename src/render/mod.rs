@@ -1,4 +1,6 @@
+pub mod bitmap;
 pub mod drawing;
 pub mod helpers;
 pub mod shapes;
 pub mod sprites;
+pub mod text;
@@ -1,48 +1,74 @@
+use crate::common::*;
 use crate::geo::*;
 use crate::render::drawing::*;
 
-#[derive(Copy, Clone, Debug, Default)]
-pub(crate) struct Rect {
-    pub(crate) p1: Point,
-    pub(crate) p2: Point,
+/// A rectangle of pixels from `p1` up to, but not including, `p2`. Corners
+/// can be negative or past the frame, so it can stand for anything from a
+/// sprite partly off screen to the part of the frame a drawing is clipped to.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Rect {
+    pub p1: Vec2<i64>,
+    pub p2: Vec2<i64>,
 }
 
 impl Rect {
-    /// Create a rectangle from two `Point`s.
-    pub(crate) fn new(p1: &Point, p2: &Point) -> Rect {
-        let p1 = *p1;
-        let p2 = *p2;
-
+    /// Create a rectangle from two corners.
+    pub fn new(p1: Vec2<i64>, p2: Vec2<i64>) -> Rect {
         Rect { p1, p2 }
     }
 
-    /// Create a rectangle from a `Point` and a `Drawable`.
-    pub(crate) fn from_drawable<D>(pos: &Point, drawable: &D) -> Rect
+    /// The whole frame.
+    pub fn screen() -> Rect {
+        Rect::new(Vec2::new(0, 0), Vec2::new(WIDTH as i64, HEIGHT as i64))
+    }
+
+    /// Create a rectangle from a position and a `Drawable`.
+    pub fn from_drawable<D>(pos: Vec2<i64>, drawable: &D) -> Rect
     where
         D: Drawable,
     {
-        let p1 = *pos;
-        let p2 = p1 + Point::new(drawable.width(), drawable.height());
+        let p2 = pos + Vec2::new(drawable.width() as i64, drawable.height() as i64);
 
-        Rect { p1, p2 }
+        Rect { p1: pos, p2 }
+    }
+
+    /// Whether the rectangle covers no pixels.
+    pub fn is_empty(&self) -> bool {
+        self.p1.x >= self.p2.x || self.p1.y >= self.p2.y
+    }
+
+    /// Whether the pixel at `x`, `y` is inside the rectangle.
+    pub fn contains(&self, x: i64, y: i64) -> bool {
+        (self.p1.x..self.p2.x).contains(&x) && (self.p1.y..self.p2.y).contains(&y)
     }
 
     /// Test for intersections between two rectangles.
     ///
     /// Rectangles intersect when the geometry of either overlaps.
-    pub(crate) fn intersects(&self, other: &Rect) -> bool {
+    pub fn intersects(&self, other: &Rect) -> bool {
         let (top1, right1, bottom1, left1) = self.get_bounds();
         let (top2, right2, bottom2, left2) = other.get_bounds();
 
         bottom1 > top2 && bottom2 > top1 && right1 > left2 && right2 > left1
     }
 
+    /// The pixels both rectangles cover, which may be empty.
+    pub fn intersection(&self, other: &Rect) -> Rect {
+        let (top1, right1, bottom1, left1) = self.get_bounds();
+        let (top2, right2, bottom2, left2) = other.get_bounds();
+
+        Rect::new(
+            Vec2::new(left1.max(left2), top1.max(top2)),
+            Vec2::new(right1.min(right2), bottom1.min(bottom2)),
+        )
+    }
+
     /// Compute the bounding box for this rectangle.
     ///
     /// # Returns
     ///
     /// Tuple of `(top, right, bottom, left)`, e.g. in CSS clockwise order.
-    fn get_bounds(&self) -> (usize, usize, usize, usize) {
+    fn get_bounds(&self) -> (i64, i64, i64, i64) {
         (self.p1.y, self.p2.x, self.p2.y, self.p1.x)
     }
 }
@@ -9,6 +9,7 @@ pub struct Sprite {
     pub pixels: Vec<u8>,
 }
 
+#[allow(dead_code)]
 #[derive(Clone, Debug)]
 pub struct SpriteRef {
     width: usize,
@@ -0,0 +1,176 @@
+use crate::course::*;
+use std::fmt;
+
+/// The name for a hole's result compared to its par.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ScoreName {
+    HoleInOne,
+    /// Four or more under par.
+    Condor,
+    Albatross,
+    Eagle,
+    Birdie,
+    Par,
+    Bogey,
+    DoubleBogey,
+    TripleBogey,
+    /// More than three over par, holding how many over.
+    Over(u32),
+}
+
+impl ScoreName {
+    pub fn from_strokes(strokes: u32, par: u32) -> ScoreName {
+        if strokes == 1 {
+            return ScoreName::HoleInOne;
+        }
+        match strokes as i64 - par as i64 {
+            i64::MIN..=-4 => ScoreName::Condor,
+            -3 => ScoreName::Albatross,
+            -2 => ScoreName::Eagle,
+            -1 => ScoreName::Birdie,
+            0 => ScoreName::Par,
+            1 => ScoreName::Bogey,
+            2 => ScoreName::DoubleBogey,
+            3 => ScoreName::TripleBogey,
+            over => ScoreName::Over(over as u32),
+        }
+    }
+}
+
+impl fmt::Display for ScoreName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScoreName::HoleInOne => write!(f, "Hole in one"),
+            ScoreName::Condor => write!(f, "Condor"),
+            ScoreName::Albatross => write!(f, "Albatross"),
+            ScoreName::Eagle => write!(f, "Eagle"),
+            ScoreName::Birdie => write!(f, "Birdie"),
+            ScoreName::Par => write!(f, "Par"),
+            ScoreName::Bogey => write!(f, "Bogey"),
+            ScoreName::DoubleBogey => write!(f, "Double bogey"),
+            ScoreName::TripleBogey => write!(f, "Triple bogey"),
+            ScoreName::Over(over) => write!(f, "+{over}"),
+        }
+    }
+}
+
+/// Strokes taken on one hole.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HoleScore {
+    pub par: u32,
+    /// Every stroke taken, penalties included.
+    pub strokes: u32,
+    /// Penalty strokes for lost balls.
+    pub penalties: u32,
+    /// Set once the ball has been sunk.
+    pub completed: bool,
+}
+
+impl HoleScore {
+    pub fn new(par: u32) -> Self {
+        Self {
+            par,
+            strokes: 0,
+            penalties: 0,
+            completed: false,
+        }
+    }
+
+    /// Strokes relative to par, negative when under.
+    pub fn to_par(&self) -> i32 {
+        self.strokes as i32 - self.par as i32
+    }
+
+    /// The name of the result, once the hole is completed.
+    pub fn name(&self) -> Option<ScoreName> {
+        if self.completed {
+            Some(ScoreName::from_strokes(self.strokes, self.par))
+        } else {
+            None
+        }
+    }
+}
+
+/// Scores for every hole of a round, in course order.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Scorecard {
+    holes: Vec<HoleScore>,
+}
+
+impl Scorecard {
+    pub fn new(pars: &[u32]) -> Self {
+        Self {
+            holes: pars.iter().map(|par| HoleScore::new(*par)).collect(),
+        }
+    }
+
+    /// A blank card with the par of every hole on `course`.
+    pub fn for_course(course: &Course) -> Self {
+        let pars: Vec<u32> = course.holes().iter().map(|level| level.par).collect();
+        Self::new(&pars)
+    }
+
+    pub fn holes(&self) -> &[HoleScore] {
+        &self.holes
+    }
+
+    /// The score for the hole at `index`, counted from zero.
+    pub fn hole(&self, index: usize) -> Option<&HoleScore> {
+        self.holes.get(index)
+    }
+
+    pub fn add_stroke(&mut self, index: usize) {
+        if let Some(hole) = self.holes.get_mut(index) {
+            hole.strokes += 1;
+        }
+    }
+
+    /// Adds a penalty stroke, counted in the hole's strokes.
+    pub fn add_penalty(&mut self, index: usize) {
+        if let Some(hole) = self.holes.get_mut(index) {
+            hole.strokes += 1;
+            hole.penalties += 1;
+        }
+    }
+
+    pub fn complete(&mut self, index: usize) {
+        if let Some(hole) = self.holes.get_mut(index) {
+            hole.completed = true;
+        }
+    }
+
+    /// Wipes the strokes for a hole that is being played again.
+    pub fn reset_hole(&mut self, index: usize) {
+        if let Some(hole) = self.holes.get_mut(index) {
+            *hole = HoleScore::new(hole.par);
+        }
+    }
+
+    /// Wipes every hole for a new round.
+    pub fn reset(&mut self) {
+        for hole in self.holes.iter_mut() {
+            *hole = HoleScore::new(hole.par);
+        }
+    }
+
+    pub fn total_strokes(&self) -> u32 {
+        self.holes.iter().map(|hole| hole.strokes).sum()
+    }
+
+    pub fn total_par(&self) -> u32 {
+        self.holes.iter().map(|hole| hole.par).sum()
+    }
+
+    /// Strokes relative to par over the completed holes.
+    pub fn to_par(&self) -> i32 {
+        self.holes
+            .iter()
+            .filter(|hole| hole.completed)
+            .map(|hole| hole.to_par())
+            .sum()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.holes.iter().all(|hole| hole.completed)
+    }
+}
@@ -0,0 +1,36 @@
+use crate::common::*;
+use crate::entities::assests::*;
+use crate::render::bitmap::*;
+use crate::screens::menus::*;
+use crate::screens::scenes::*;
+
+/// Shown once the last hole of the course has been sunk.
+pub struct EndScreen {
+    pixels: Vec<u8>,
+}
+
+impl Default for EndScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EndScreen {
+    pub fn new() -> Self {
+        Self {
+            pixels: decode_mono(&END_SCREEN, WIDTH, HEIGHT, MONO_FOREGROUND, MONO_BACKGROUND),
+        }
+    }
+
+    /// Any of Select or Back goes back to the start menu.
+    pub fn press(&mut self, key: MenuKey) -> Transition {
+        match key {
+            MenuKey::Select | MenuKey::Back => Transition::ToStart,
+            _ => Transition::Stay,
+        }
+    }
+
+    pub fn draw(&self, frame: &mut [u8]) {
+        frame.copy_from_slice(&self.pixels);
+    }
+}
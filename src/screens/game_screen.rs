@@ -1,46 +1,66 @@
 use crate::controls::*;
 use crate::game::*;
+use crate::game_info::*;
 use crate::geo::*;
-use pixels::Pixels;
+use crate::render::helpers::*;
+use crate::render::shapes::*;
+use crate::render::text::*;
+use crate::screens::menus::*;
+use crate::screens::scenes::*;
+use log::debug;
 use winit::event::VirtualKeyCode;
 use winit_input_helper::WinitInputHelper;
+
+/// How much of its brightness the game keeps behind the pause menu.
+const PAUSE_DIM: f64 = 0.4;
+const PAUSE_TEXT_COLOR: [u8; 4] = [0xff, 0xff, 0xff, 0xff];
+/// Pixels between the pause menu and the text above and below it.
+const PAUSE_TEXT_GAP: i64 = 16;
+
 pub struct GameScreen {
-    pub pixels: Pixels,
     pub game: GameState,
-    // pub info: InfoScreen,
+    /// The information strip below the hole.
+    pub info: InfoScreen,
     pub controls: Controls,
-    pub input: WinitInputHelper,
     pub paused: bool,
+    /// Shown over the game while it is paused.
+    pub pause_menu: Menu,
 }
 
 impl GameScreen {
-    pub fn new(pixels: Pixels, debug: bool) -> Self {
-        let game = GameState::new();
+    pub fn new(game: GameState, _debug: bool) -> Self {
         Self {
-            pixels,
-            game: game,
-            //   info: InfoScreen::new(),
+            info: InfoScreen::from_game(&game),
+            game,
             controls: Controls::default(),
-            input: WinitInputHelper::new(),
             paused: false,
+            pause_menu: Menu::new(vec![
+                MenuOptions::Resume,
+                MenuOptions::Restart,
+                MenuOptions::Settings,
+                MenuOptions::QuitToMenu,
+            ]),
         }
     }
-    pub fn update_controls(&mut self) {
-        // Pump the gilrs event loop and find an active gamepad
+    /// Reads the keyboard into `controls`. Presses are kept until a physics
+    /// update uses them, so none are lost or repeated whatever the frame rate.
+    pub fn update_controls(&mut self, input: &WinitInputHelper) {
+        let pending = self.controls.clone();
         self.controls = {
             // Keyboard controls
-            let shift = self.input.key_held(VirtualKeyCode::LShift);
-            let left = self.input.key_held(VirtualKeyCode::Left);
-            let right = self.input.key_held(VirtualKeyCode::Right);
-            let up = self.input.key_pressed(VirtualKeyCode::Up);
-            let down = self.input.key_pressed(VirtualKeyCode::Down);
-            let hit = self.input.key_pressed(VirtualKeyCode::Space);
-            let pause = self.input.key_pressed(VirtualKeyCode::Pause)
-                | self.input.key_pressed(VirtualKeyCode::P);
-            let restart = self.input.key_pressed(VirtualKeyCode::Back);
-            if pause {
-                self.paused = !self.paused;
-            }
+            let shift = input.key_held(VirtualKeyCode::LShift);
+            let left = input.key_held(VirtualKeyCode::Left);
+            let right = input.key_held(VirtualKeyCode::Right);
+            let up = input.key_pressed(VirtualKeyCode::Up);
+            let down = input.key_pressed(VirtualKeyCode::Down);
+            let hit = input.key_pressed(VirtualKeyCode::Space) || pending.hit;
+            let restart =
+                input.key_pressed(VirtualKeyCode::Back) || input.key_pressed(VirtualKeyCode::R);
+            let held = |key| input.key_held(key) as i8;
+            let pan = Vec2::new(
+                held(VirtualKeyCode::D) - held(VirtualKeyCode::A),
+                held(VirtualKeyCode::S) - held(VirtualKeyCode::W),
+            );
             if restart {
                 self.reset_game();
             }
@@ -57,7 +77,7 @@ impl GameScreen {
             } else if down {
                 PowerLevel::Down
             } else {
-                PowerLevel::Same
+                pending.power
             };
 
             let adj = if shift {
@@ -71,12 +91,111 @@ impl GameScreen {
                 power,
                 adj,
                 hit,
+                pan,
             }
         };
     }
 
+    /// Pauses on Back or Pause. While paused the pause menu takes the keys,
+    /// and Back or Pause play on. Settings changes the aiming guide length
+    /// and stays paused.
+    pub fn press(&mut self, key: MenuKey) -> Transition {
+        if !self.paused {
+            if matches!(key, MenuKey::Back | MenuKey::Pause) {
+                self.pause();
+            }
+            return Transition::Stay;
+        }
+        if matches!(key, MenuKey::Back | MenuKey::Pause) {
+            self.paused = false;
+            return Transition::Stay;
+        }
+        match self.pause_menu.press(key) {
+            Some(MenuOptions::Resume) => {
+                self.paused = false;
+                Transition::Stay
+            }
+            Some(MenuOptions::Restart) => {
+                self.game.restart_hole();
+                self.info.update(&self.game);
+                self.paused = false;
+                Transition::Stay
+            }
+            Some(MenuOptions::Settings) => {
+                self.game.preview = self.game.preview.next();
+                Transition::Stay
+            }
+            Some(MenuOptions::QuitToMenu) => Transition::ToStart,
+            _ => Transition::Stay,
+        }
+    }
+
+    /// Freezes the game where it is, with the pause menu on its first option.
+    /// Keys pressed but not yet used are dropped, so nothing happens on the
+    /// first update after playing on.
+    pub fn pause(&mut self) {
+        self.paused = true;
+        self.controls = Controls::default();
+        self.pause_menu = Menu::new(self.pause_menu.options().to_vec());
+    }
+
+    pub fn update(&mut self) -> Transition {
+        if self.paused {
+            return Transition::Stay;
+        }
+        self.game.update(&self.controls);
+        self.controls.hit = false;
+        self.controls.power = PowerLevel::Same;
+        self.info.update(&self.game);
+        for event in self.game.take_events() {
+            debug!("{event:?}");
+        }
+        if self.game.state == GolfState::Finished {
+            Transition::ToEnd
+        } else {
+            Transition::Stay
+        }
+    }
+
+    /// Restarts the current hole, or the whole round once it is over.
     pub fn reset_game(&mut self) {
-        self.game.state = GolfState::Aiming;
-        self.game.ball.reset_at(Point::new(28, 30));
+        if self.game.state == GolfState::Finished {
+            self.game.restart_course();
+        } else {
+            self.game.restart_hole();
+        }
+        self.info.update(&self.game);
+    }
+
+    /// Draws the current frame. `alpha` is how far into the next physics
+    /// update the frame falls, from 0 to 1. While paused there is no next
+    /// update, so the game is drawn as it stands.
+    pub fn draw(&self, frame: &mut [u8], alpha: f64) {
+        let alpha = if self.paused { 1.0 } else { alpha };
+        self.game.draw(frame, alpha);
+        self.info.draw(frame);
+        if self.paused {
+            self.draw_pause(frame);
+        }
+    }
+
+    /// Dims the game and draws the pause menu over it, with the settings
+    /// as they are below it.
+    fn draw_pause(&self, frame: &mut [u8]) {
+        let screen = Rect::screen();
+        dim(frame, &screen, &screen, PAUSE_DIM);
+        self.pause_menu.draw(frame);
+
+        let count = self.pause_menu.options().len();
+        let top = self.pause_menu.option_area(0).p1.y;
+        let bottom = self.pause_menu.option_area(count.saturating_sub(1)).p2.y;
+        let title = Rect::new(
+            Vec2::new(0, top - PAUSE_TEXT_GAP - GLYPH_SIZE),
+            Vec2::new(screen.p2.x, top),
+        );
+        draw_text(frame, &title, "PAUSED", PAUSE_TEXT_COLOR, Align::Center);
+        let settings = Rect::new(Vec2::new(0, bottom + PAUSE_TEXT_GAP), screen.p2);
+        let text = format!("AIM GUIDE {}", self.game.preview.name());
+        draw_text(frame, &settings, &text, PAUSE_TEXT_COLOR, Align::Center);
     }
 }
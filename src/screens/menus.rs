@@ -1,9 +1,169 @@
+use crate::common::*;
+use crate::geo::*;
+use crate::render::helpers::*;
+use crate::render::shapes::*;
+use crate::render::text::*;
+
+/// Size in pixels of one option of a menu.
+const OPTION_WIDTH: i64 = 160;
+const OPTION_HEIGHT: i64 = 20;
+/// Gap in pixels between options.
+const OPTION_GAP: i64 = 8;
+const OPTION_COLOR: [u8; 4] = [0x30, 0x30, 0x40, 0xff];
+const SELECTED_COLOR: [u8; 4] = [0xff, 0xc4, 0x00, 0xff];
+const OUTLINE_COLOR: [u8; 4] = [0xff, 0xff, 0xff, 0xff];
+
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum MenuOptions {
     Start,
     Quit,
+    /// Plays on from where the game was paused.
+    Resume,
     Restart,
-    NoSelection
+    /// Changes how the game plays, such as the length of the aiming guide.
+    Settings,
+    /// Leaves the round for the start menu.
+    QuitToMenu,
+    NoSelection,
+}
+
+impl MenuOptions {
+    /// Text drawn on the option's box.
+    pub fn label(&self) -> &'static str {
+        match self {
+            MenuOptions::Start => "START",
+            MenuOptions::Quit => "QUIT",
+            MenuOptions::Resume => "RESUME",
+            MenuOptions::Restart => "RESTART HOLE",
+            MenuOptions::Settings => "SETTINGS",
+            MenuOptions::QuitToMenu => "QUIT TO MENU",
+            MenuOptions::NoSelection => "",
+        }
+    }
 }
 
 pub trait HasOptions {}
-impl HasOptions for MenuOptions {}
\ No newline at end of file
+impl HasOptions for MenuOptions {}
+
+/// Keys that move around menus and between screens.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum MenuKey {
+    Up,
+    Down,
+    /// Enter or Space.
+    Select,
+    /// Escape.
+    Back,
+    /// P or Pause.
+    Pause,
+}
+
+/// Pixels between a placed option and the highlight drawn around it.
+const HIGHLIGHT_MARGIN: i64 = 3;
+
+/// A column of options with one of them highlighted, moved through with
+/// Up and Down.
+#[derive(Clone, Debug)]
+pub struct Menu {
+    options: Vec<MenuOptions>,
+    /// Where each option already is on the screen behind the menu, or empty
+    /// for options drawn by the menu itself.
+    areas: Vec<Rect>,
+    selected: usize,
+}
+
+impl Menu {
+    /// A menu of `options` with the first one highlighted.
+    pub fn new(options: Vec<MenuOptions>) -> Self {
+        Self {
+            options,
+            areas: Vec::new(),
+            selected: 0,
+        }
+    }
+
+    /// A menu over buttons already drawn at `areas`, one for each option.
+    /// Only the highlight is drawn.
+    pub fn placed(options: Vec<MenuOptions>, areas: Vec<Rect>) -> Self {
+        debug_assert_eq!(options.len(), areas.len());
+        Self {
+            options,
+            areas,
+            selected: 0,
+        }
+    }
+
+    pub fn options(&self) -> &[MenuOptions] {
+        &self.options
+    }
+
+    /// The highlighted option.
+    pub fn selected(&self) -> MenuOptions {
+        self.options
+            .get(self.selected)
+            .copied()
+            .unwrap_or(MenuOptions::NoSelection)
+    }
+
+    /// Moves the highlight for Up and Down, wrapping around at either end,
+    /// and returns the highlighted option for Select.
+    pub fn press(&mut self, key: MenuKey) -> Option<MenuOptions> {
+        let count = self.options.len();
+        if count == 0 {
+            return None;
+        }
+        match key {
+            MenuKey::Up => self.selected = (self.selected + count - 1) % count,
+            MenuKey::Down => self.selected = (self.selected + 1) % count,
+            MenuKey::Select => return Some(self.selected()),
+            MenuKey::Back | MenuKey::Pause => {}
+        }
+        None
+    }
+
+    /// Where option `index` is drawn, stacked in the middle of the screen
+    /// unless the menu was placed.
+    pub fn option_area(&self, index: usize) -> Rect {
+        if let Some(area) = self.areas.get(index) {
+            return *area;
+        }
+        let count = self.options.len() as i64;
+        let height = count * OPTION_HEIGHT + (count - 1).max(0) * OPTION_GAP;
+        let left = (WIDTH as i64 - OPTION_WIDTH) / 2;
+        let top = (HEIGHT as i64 - height) / 2 + index as i64 * (OPTION_HEIGHT + OPTION_GAP);
+        Rect::new(
+            Vec2::new(left, top),
+            Vec2::new(left + OPTION_WIDTH, top + OPTION_HEIGHT),
+        )
+    }
+
+    pub fn draw(&self, frame: &mut [u8]) {
+        let screen = Rect::screen();
+        if !self.areas.is_empty() {
+            let area = self.option_area(self.selected);
+            for grow in HIGHLIGHT_MARGIN..HIGHLIGHT_MARGIN + 2 {
+                let around = Rect::new(
+                    area.p1 - Vec2::new(grow, grow),
+                    area.p2 + Vec2::new(grow, grow),
+                );
+                rect(frame, &screen, &around, SELECTED_COLOR);
+            }
+            return;
+        }
+        for (index, option) in self.options.iter().enumerate() {
+            let area = self.option_area(index);
+            let label_color = if index == self.selected {
+                fill_rect(frame, &screen, &area, SELECTED_COLOR);
+                rect(frame, &screen, &area, OUTLINE_COLOR);
+                OPTION_COLOR
+            } else {
+                fill_rect(frame, &screen, &area, OPTION_COLOR);
+                OUTLINE_COLOR
+            };
+            // Centered down the box as well as across it.
+            let top = area.p1.y + (OPTION_HEIGHT - GLYPH_SIZE) / 2;
+            let label = Rect::new(Vec2::new(area.p1.x, top), area.p2);
+            draw_text(frame, &label, option.label(), label_color, Align::Center);
+        }
+    }
+}
@@ -1,4 +1,5 @@
 pub mod end_screen;
 pub mod game_screen;
 pub mod menus;
+pub mod scenes;
 pub mod start_screen;
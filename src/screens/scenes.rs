@@ -0,0 +1,129 @@
+//! Which screen is showing and how the game moves between them: the start
+//! menu starts a round, the game can be paused over itself and ends on the
+//! end screen, which goes back to the start menu.
+use crate::course::*;
+use crate::game::*;
+use crate::screens::end_screen::*;
+use crate::screens::game_screen::*;
+use crate::screens::menus::*;
+use crate::screens::start_screen::*;
+use winit::event::VirtualKeyCode;
+use winit_input_helper::WinitInputHelper;
+
+/// Keyboard keys read as menu keys, checked in this order. Left and Right
+/// move like Up and Down, for menus laid out in a row.
+const MENU_KEYS: [(VirtualKeyCode, MenuKey); 9] = [
+    (VirtualKeyCode::Up, MenuKey::Up),
+    (VirtualKeyCode::Down, MenuKey::Down),
+    (VirtualKeyCode::Left, MenuKey::Up),
+    (VirtualKeyCode::Right, MenuKey::Down),
+    (VirtualKeyCode::Return, MenuKey::Select),
+    (VirtualKeyCode::Space, MenuKey::Select),
+    (VirtualKeyCode::Escape, MenuKey::Back),
+    (VirtualKeyCode::P, MenuKey::Pause),
+    (VirtualKeyCode::Pause, MenuKey::Pause),
+];
+
+/// Where a screen asks to go after a key press or an update.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Transition {
+    Stay,
+    ToStart,
+    /// Start a new round of the course.
+    ToGame,
+    ToEnd,
+    /// Close the game.
+    Quit,
+}
+
+/// The screen being shown.
+pub enum Screen {
+    Start(StartMenu),
+    Game(Box<GameScreen>),
+    End(EndScreen),
+}
+
+/// Owns the current screen and hands it input, updates and drawing.
+pub struct SceneManager {
+    pub screen: Screen,
+    /// The holes every new round is played on.
+    course: Course,
+    debug: bool,
+    quit: bool,
+}
+
+impl SceneManager {
+    /// Opens on the start menu, with rounds played on `course`.
+    pub fn new(course: Course, debug: bool) -> Self {
+        Self {
+            screen: Screen::Start(StartMenu::new()),
+            course,
+            debug,
+            quit: false,
+        }
+    }
+
+    /// Whether a screen has asked to close the game.
+    pub fn should_quit(&self) -> bool {
+        self.quit
+    }
+
+    /// Reads the keyboard. The game takes its controls first, so the key
+    /// that starts a round is not also played as a shot.
+    pub fn read_input(&mut self, input: &WinitInputHelper) {
+        if let Screen::Game(screen) = &mut self.screen {
+            if !screen.paused {
+                screen.update_controls(input);
+            }
+        }
+        for (code, key) in MENU_KEYS {
+            if input.key_pressed(code) {
+                self.press(key);
+            }
+        }
+    }
+
+    /// Hands `key` to the current screen and follows where it leads.
+    pub fn press(&mut self, key: MenuKey) {
+        let transition = match &mut self.screen {
+            Screen::Start(screen) => screen.press(key),
+            Screen::Game(screen) => screen.press(key),
+            Screen::End(screen) => screen.press(key),
+        };
+        self.go(transition);
+    }
+
+    /// Runs one physics update of the current screen.
+    pub fn update(&mut self) {
+        if let Screen::Game(screen) = &mut self.screen {
+            let transition = screen.update();
+            self.go(transition);
+        }
+    }
+
+    /// Draws the current screen. `alpha` is how far into the next physics
+    /// update the frame falls, from 0 to 1.
+    pub fn draw(&self, frame: &mut [u8], alpha: f64) {
+        match &self.screen {
+            Screen::Start(screen) => screen.draw(frame),
+            Screen::Game(screen) => screen.draw(frame, alpha),
+            Screen::End(screen) => screen.draw(frame),
+        }
+    }
+
+    fn go(&mut self, transition: Transition) {
+        self.screen = match transition {
+            Transition::Stay => return,
+            Transition::ToStart => Screen::Start(StartMenu::new()),
+            Transition::ToGame => {
+                let game = GameState::from_course(self.course.clone());
+                Screen::Game(Box::new(GameScreen::new(game, self.debug)))
+            }
+            Transition::ToEnd => Screen::End(EndScreen::new()),
+            Transition::Quit => {
+                self.quit = true;
+                return;
+            }
+        };
+    }
+}
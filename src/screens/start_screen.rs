@@ -1,32 +1,79 @@
+use crate::common::*;
+use crate::entities::assests::*;
+use crate::geo::*;
+use crate::render::bitmap::*;
 use crate::render::drawing::*;
+use crate::render::shapes::*;
 use crate::screens::menus::*;
+use crate::screens::scenes::*;
 
+/// Where the START and QUIT buttons are on the splash screen.
+const START_BUTTON: Rect = Rect {
+    p1: Vec2 { x: 148, y: 337 },
+    p2: Vec2 { x: 301, y: 390 },
+};
+const QUIT_BUTTON: Rect = Rect {
+    p1: Vec2 { x: 352, y: 336 },
+    p2: Vec2 { x: 500, y: 390 },
+};
+
+/// The title screen, where a round is started or the game left.
+/// The options are the splash screen's own START and QUIT buttons.
 pub struct StartMenu {
-    state: MenuOptions,
+    pub menu: Menu,
     width: usize,
     height: usize,
-    pixels: [u8; 1024000],
+    pixels: Vec<u8>,
+}
+
+impl Default for StartMenu {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl StartMenu {
     pub fn new() -> Self {
         Self {
-            state: MenuOptions::NoSelection,
-            width: 640,
-            height: 400,
-            pixels: [128; 1024000],
+            menu: Menu::placed(
+                vec![MenuOptions::Start, MenuOptions::Quit],
+                vec![START_BUTTON, QUIT_BUTTON],
+            ),
+            width: WIDTH,
+            height: HEIGHT,
+            pixels: decode_mono(
+                &SPLASH_SCREEN,
+                WIDTH,
+                HEIGHT,
+                MONO_FOREGROUND,
+                MONO_BACKGROUND,
+            ),
         }
     }
+
+    pub fn press(&mut self, key: MenuKey) -> Transition {
+        match self.menu.press(key) {
+            Some(MenuOptions::Start) => Transition::ToGame,
+            Some(MenuOptions::Quit) => Transition::Quit,
+            _ if key == MenuKey::Back => Transition::Quit,
+            _ => Transition::Stay,
+        }
+    }
+
+    pub fn draw(&self, frame: &mut [u8]) {
+        frame.copy_from_slice(&self.pixels);
+        self.menu.draw(frame);
+    }
 }
 
 impl Drawable for StartMenu {
     fn width(&self) -> usize {
-        return self.width;
+        self.width
     }
     fn height(&self) -> usize {
-        return self.height;
+        self.height
     }
     fn pixels(&self) -> &[u8] {
-        return &self.pixels;
+        &self.pixels
     }
 }
@@ -0,0 +1,83 @@
+//! Runs a `GameState` without a window, GPU or input devices. Useful for
+//! testing physics, scoring and levels under `cargo test`.
+use crate::controls::*;
+use crate::game::*;
+use crate::geo::*;
+
+/// What the game looked like after one tick.
+#[derive(Clone, Debug)]
+pub struct TickRecord {
+    pub tick: usize,
+    /// Sub-pixel center of the ball.
+    pub position: Vec2<f64>,
+    pub state: GolfState,
+    pub events: Vec<GameEvent>,
+}
+
+/// The outcome of a simulation run.
+#[derive(Clone, Debug)]
+pub struct Simulation {
+    pub ticks: Vec<TickRecord>,
+    pub state: GameState,
+}
+
+impl Simulation {
+    /// Every event in the order it happened.
+    pub fn events(&self) -> impl Iterator<Item = &GameEvent> {
+        self.ticks.iter().flat_map(|record| record.events.iter())
+    }
+
+    /// Every ball position in tick order.
+    pub fn positions(&self) -> impl Iterator<Item = Vec2<f64>> + '_ {
+        self.ticks.iter().map(|record| record.position)
+    }
+
+    /// The first tick that produced an event matching `predicate`.
+    pub fn first_tick_with<P>(&self, mut predicate: P) -> Option<&TickRecord>
+    where
+        P: FnMut(&GameEvent) -> bool,
+    {
+        self.ticks
+            .iter()
+            .find(|record| record.events.iter().any(&mut predicate))
+    }
+}
+
+/// Runs `state` for `ticks` updates, feeding it `script[tick]` on each one.
+/// Ticks past the end of the script get the default, idle, controls.
+pub fn simulate(state: GameState, script: &[Controls], ticks: usize) -> Simulation {
+    simulate_with(state, ticks, |tick, _| {
+        script.get(tick).cloned().unwrap_or_default()
+    })
+}
+
+/// Runs `state` for `ticks` updates, asking `controls` for the input of each
+/// one. It sees the state before the update, so scripts can react to it.
+pub fn simulate_with<F>(mut state: GameState, ticks: usize, mut controls: F) -> Simulation
+where
+    F: FnMut(usize, &GameState) -> Controls,
+{
+    let mut records = Vec::with_capacity(ticks);
+    for tick in 0..ticks {
+        let input = controls(tick, &state);
+        state.update(&input);
+        records.push(TickRecord {
+            tick,
+            position: state.ball.exact_center(),
+            state: state.state,
+            events: state.take_events(),
+        });
+    }
+    Simulation {
+        ticks: records,
+        state,
+    }
+}
+
+/// Controls that hit the ball and do nothing else.
+pub fn hit() -> Controls {
+    Controls {
+        hit: true,
+        ..Controls::default()
+    }
+}
@@ -0,0 +1,94 @@
+use paga::common::*;
+use paga::course::*;
+use paga::entities::assests::*;
+use paga::game::*;
+use paga::render::bitmap::*;
+use paga::screens::menus::*;
+use paga::screens::scenes::*;
+
+const INK: [u8; 4] = [1, 2, 3, 255];
+const PAPER: [u8; 4] = [9, 8, 7, 255];
+
+fn pixel(pixels: &[u8], width: usize, x: usize, y: usize) -> [u8; 4] {
+    let i = (x + y * width) * 4;
+    pixels[i..i + 4].try_into().unwrap()
+}
+
+#[test]
+fn reads_the_top_bit_as_the_leftmost_pixel() {
+    let pixels = decode_mono(&[0x8000_0001u32], 32, 1, INK, PAPER);
+    assert_eq!(pixels.len(), 32 * 4);
+    assert_eq!(pixel(&pixels, 32, 0, 0), INK);
+    assert!((1..31).all(|x| pixel(&pixels, 32, x, 0) == PAPER));
+    assert_eq!(pixel(&pixels, 32, 31, 0), INK);
+}
+
+#[test]
+fn starts_each_row_on_a_new_word() {
+    // 40 pixels wide takes two words a row, the last 24 bits of the second
+    // are padding.
+    let words = [0u32, 0x00ff_ffff, 0x8000_0000, 0];
+    let pixels = decode_mono(&words, 40, 2, INK, PAPER);
+    assert_eq!(pixels.len(), 40 * 2 * 4);
+    assert!((0..40).all(|x| pixel(&pixels, 40, x, 0) == PAPER));
+    assert_eq!(pixel(&pixels, 40, 0, 1), INK);
+    assert!((1..40).all(|x| pixel(&pixels, 40, x, 1) == PAPER));
+}
+
+#[test]
+fn reads_only_the_low_32_bits_of_wide_words() {
+    let pixels = decode_mono(&[0xffff_ffff_0000_0000u64, 0x4000_0000], 32, 2, INK, PAPER);
+    assert!((0..32).all(|x| pixel(&pixels, 32, x, 0) == PAPER));
+    assert_eq!(pixel(&pixels, 32, 1, 1), INK);
+}
+
+#[test]
+fn fills_missing_words_with_background() {
+    let pixels = decode_mono(&[0xffff_ffffu32], 32, 3, INK, PAPER);
+    assert!((0..32).all(|x| pixel(&pixels, 32, x, 0) == INK));
+    assert!((0..32).all(|x| pixel(&pixels, 32, x, 2) == PAPER));
+    assert!(decode_mono::<u32>(&[], 0, 5, INK, PAPER).is_empty());
+}
+
+#[test]
+fn decodes_the_original_screens() {
+    for pixels in [
+        decode_mono(&SPLASH_SCREEN, WIDTH, HEIGHT, INK, PAPER),
+        decode_mono(&END_SCREEN, WIDTH, HEIGHT, INK, PAPER),
+    ] {
+        assert_eq!(pixels.len(), WIDTH * HEIGHT * 4);
+        // Blank sky in the top left, solid ground along the bottom.
+        assert_eq!(pixel(&pixels, WIDTH, 0, 0), PAPER);
+        assert!((0..WIDTH).all(|x| pixel(&pixels, WIDTH, x, HEIGHT - 1) == INK));
+    }
+}
+
+#[test]
+fn shows_the_splash_and_end_screens() {
+    let mut frame = vec![0; WIDTH * HEIGHT * 4];
+    let mut scenes = SceneManager::new(Course::standard(), false);
+    scenes.draw(&mut frame, 0.0);
+    let splash = decode_mono(
+        &SPLASH_SCREEN,
+        WIDTH,
+        HEIGHT,
+        MONO_FOREGROUND,
+        MONO_BACKGROUND,
+    );
+    // Everything but the highlight round the START button is the splash.
+    let differing = frame
+        .chunks_exact(4)
+        .zip(splash.chunks_exact(4))
+        .filter(|(a, b)| a != b)
+        .count();
+    assert!(differing > 0 && differing < 2000, "{differing}");
+
+    scenes.press(MenuKey::Select);
+    if let Screen::Game(screen) = &mut scenes.screen {
+        screen.game.state = GolfState::Finished;
+    }
+    scenes.update();
+    scenes.draw(&mut frame, 0.0);
+    let end = decode_mono(&END_SCREEN, WIDTH, HEIGHT, MONO_FOREGROUND, MONO_BACKGROUND);
+    assert!(frame == end);
+}
@@ -0,0 +1,137 @@
+use paga::camera::*;
+use paga::common::*;
+use paga::controls::*;
+use paga::game::*;
+use paga::geo::*;
+use paga::map::level::*;
+use paga::sim::*;
+use std::f64::consts::PI;
+
+/// An empty walled field of `width` by `height` tiles, teed up at `tee`.
+fn field(width: usize, height: usize, tee: (usize, usize)) -> GameState {
+    let mut level = format!(
+        "size {width} {height}\ntee {} {}\npar 3\n---\n",
+        tee.0, tee.1
+    );
+    for row in 0..height {
+        for column in 0..width {
+            let edge = row == 0 || column == 0 || row == height - 1 || column == width - 1;
+            level.push(if edge { '#' } else { '.' });
+        }
+        level.push('\n');
+    }
+    GameState::from_level(Level::parse(&level).unwrap())
+}
+
+fn pan(x: i8, y: i8) -> Controls {
+    Controls {
+        pan: Vec2::new(x, y),
+        ..Controls::default()
+    }
+}
+
+fn in_view(camera: &Camera, at: Vec2<f64>) -> bool {
+    let end = camera.position + camera.size;
+    (camera.position.x..=end.x).contains(&at.x) && (camera.position.y..=end.y).contains(&at.y)
+}
+
+#[test]
+fn starts_centered_on_the_tee() {
+    let game = field(80, 40, (40, 20));
+    let center = game.ball.exact_center();
+    let middle = game.camera.position + game.camera.size.scale(0.5);
+    assert!((middle.x - center.x).abs() < 1.0, "{middle:?} {center:?}");
+    assert!((middle.y - center.y).abs() < 1.0, "{middle:?} {center:?}");
+}
+
+#[test]
+fn stays_on_the_map_near_its_edges() {
+    let game = field(80, 40, (2, 2));
+    assert_eq!(game.camera.position, Vec2::new(0.0, 0.0));
+
+    let game = field(80, 40, (77, 37));
+    let far = Vec2::new(80.0 * 16.0, 40.0 * 16.0) - game.camera.size;
+    assert_eq!(game.camera.position, far);
+}
+
+#[test]
+fn follows_a_rolling_ball() {
+    let mut game = field(120, 23, (3, 11));
+    game.ball.angle.theta = PI;
+    game.ball.power.power = 10.0;
+    let start = game.camera.position;
+    let sim = simulate_with(game, 600, |tick, state| {
+        assert!(
+            in_view(&state.camera, state.ball.exact_center()),
+            "tick {tick}: {:?} not in {:?}",
+            state.ball.exact_center(),
+            state.camera
+        );
+        if tick == 0 {
+            hit()
+        } else {
+            Controls::default()
+        }
+    });
+    assert!(sim.state.camera.position.x > start.x + 300.0);
+}
+
+#[test]
+fn pans_while_aiming_within_the_map() {
+    let game = field(80, 40, (2, 2));
+    let sim = simulate_with(game, 60, |_, _| pan(1, 1));
+    let moved = sim.state.camera.position;
+    assert!((moved.x - PAN_SPEED / 2.0).abs() < 1.0, "{moved:?}");
+    assert!((moved.y - PAN_SPEED / 2.0).abs() < 1.0, "{moved:?}");
+
+    let sim = simulate_with(sim.state, 600, |_, _| pan(1, 1));
+    let far = Vec2::new(80.0 * 16.0, 40.0 * 16.0) - sim.state.camera.size;
+    assert_eq!(sim.state.camera.position, far);
+
+    let sim = simulate_with(sim.state, 600, |_, _| pan(-1, -1));
+    assert_eq!(sim.state.camera.position, Vec2::new(0.0, 0.0));
+}
+
+#[test]
+fn centers_holes_smaller_than_the_screen() {
+    let game = field(20, 7, (3, 3));
+    let centered = Vec2::new(
+        (20.0 * 16.0 - WIDTH as f64) / 2.0,
+        (7.0 * 16.0 - VIEW_HEIGHT as f64) / 2.0,
+    );
+    assert_eq!(game.camera.position, centered);
+    let sim = simulate_with(game, 60, |_, _| pan(1, -1));
+    assert_eq!(sim.state.camera.position, centered);
+}
+
+#[test]
+fn restarting_looks_at_the_tee_again() {
+    let game = field(80, 40, (2, 2));
+    let mut sim = simulate_with(game, 120, |_, _| pan(1, 1));
+    sim.state.restart_hole();
+    assert_eq!(sim.state.camera.position, Vec2::new(0.0, 0.0));
+}
+
+#[test]
+fn draws_any_part_of_a_large_hole() {
+    let mut frame = vec![0; WIDTH * HEIGHT * 4];
+    let mut game = field(80, 40, (2, 2));
+    for &(x, y) in &[(0.0, 0.0), (333.5, 101.25), (10_000.0, 10_000.0)] {
+        game.camera.position = Vec2::new(0.0, 0.0);
+        game.camera.pan(Vec2::new(x, y));
+        game.draw(&mut frame, 0.5);
+    }
+    // The strip below the view is left alone.
+    assert!(frame[WIDTH * VIEW_HEIGHT * 4..].iter().all(|&c| c == 0));
+    let small = field(20, 7, (3, 3));
+    small.draw(&mut frame, 0.5);
+}
+
+#[test]
+fn maps_positions_onto_the_screen() {
+    let mut camera = Camera::new(Vec2::new(100.0, 50.0), Vec2::new(400.0, 200.0));
+    camera.pan(Vec2::new(30.4, 20.6));
+    assert_eq!(camera.offset(), (30, 21));
+    assert_eq!(camera.to_screen(Vec2::new(30.0, 21.0)), (0, 0));
+    assert_eq!(camera.to_screen(Vec2::new(10.9, 60.2)), (-20, 39));
+}
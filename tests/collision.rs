@@ -0,0 +1,395 @@
+use paga::common::*;
+use paga::game::*;
+use paga::geo::*;
+use paga::map::level::*;
+use paga::physics::*;
+use paga::sim::*;
+use std::f64::consts::PI;
+
+/// An open room with a one tile thick wall down the middle.
+const THIN_WALL: &str = "\
+size 12 7
+tee 2 3
+par 3
+---
+############
+#....#.....#
+#....#.....#
+#....#.....#
+#....#.....#
+#....#.....#
+############
+";
+
+/// An open room with a single wall block in it, for corner hits.
+const PILLAR: &str = "\
+size 12 9
+tee 2 2
+par 3
+---
+############
+#..........#
+#..........#
+#..........#
+#.....#....#
+#..........#
+#..........#
+#..........#
+############
+";
+
+const RADIUS: f64 = 4.0;
+
+fn level(source: &str) -> Level {
+    Level::parse(source).unwrap()
+}
+
+/// Whether a ball centered on `center` overlaps a solid tile.
+fn overlaps_wall(level: &Level, center: Vec2<f64>) -> bool {
+    let grid = &level.map.tile_grid;
+    let column = (center.x / 16.0).floor() as isize;
+    let row = (center.y / 16.0).floor() as isize;
+    for y in row - 1..=row + 1 {
+        for x in column - 1..=column + 1 {
+            if !grid.is_solid(x, y) {
+                continue;
+            }
+            let (left, top) = (x as f64 * 16.0, y as f64 * 16.0);
+            let closest = Vec2::new(
+                center.x.clamp(left, left + 16.0),
+                center.y.clamp(top, top + 16.0),
+            );
+            if (center - closest).length() < RADIUS - 1e-3 {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+#[test]
+fn fast_ball_stops_at_a_thin_wall() {
+    let level = level(THIN_WALL);
+    // Far more than a tile in one step.
+    let contact = sweep_circle(
+        &level.map.tile_grid,
+        Vec2::new(60.0, 56.0),
+        RADIUS,
+        Vec2::new(200.0, 0.0),
+    )
+    .expect("should hit the wall");
+    assert_eq!(contact.tile, (5, 3));
+    assert_eq!(contact.normal, Vec2::new(-1.0, 0.0));
+    // The ball's right edge lands on the wall at x = 80.
+    assert!((60.0 + 200.0 * contact.time + RADIUS - 80.0).abs() < 1e-9);
+}
+
+#[test]
+fn bounce_keeps_the_leftover_motion() {
+    let level = level(THIN_WALL);
+    // 2 pixels from the wall, moving 10 pixels this step.
+    let sweep = move_circle(
+        &level.map.tile_grid,
+        Vec2::new(74.0, 56.0),
+        RADIUS,
+        Vec2::new(10.0 / PHYSICS_DT, 0.0),
+        PHYSICS_DT,
+    );
+    // The last 8 pixels are spent going back, slowed by the wall.
+    let restitution = level.map.tile_grid.tile_at(5, 3).restitution();
+    assert_eq!(sweep.contacts.len(), 1);
+    assert!(
+        (sweep.center.x - (76.0 - 8.0 * restitution)).abs() < 1e-4,
+        "{:?}",
+        sweep.center
+    );
+    assert!((sweep.velocity.x + restitution * 10.0 / PHYSICS_DT).abs() < 1e-6);
+}
+
+#[test]
+fn sliding_along_a_wall_does_not_catch_on_tile_seams() {
+    let level = level(THIN_WALL);
+    // Touching the floor and rolling along it.
+    let sweep = move_circle(
+        &level.map.tile_grid,
+        Vec2::new(24.0, 92.0),
+        RADIUS,
+        Vec2::new(30.0 / PHYSICS_DT, 0.0),
+        PHYSICS_DT,
+    );
+    assert!(sweep.contacts.is_empty());
+    assert_eq!(sweep.center, Vec2::new(54.0, 92.0));
+}
+
+#[test]
+fn square_corner_hit_bounces_straight_back() {
+    let level = level(PILLAR);
+    // The pillar's top left corner is at (96, 64). Aim the ball straight
+    // at it along the diagonal.
+    let sweep = move_circle(
+        &level.map.tile_grid,
+        Vec2::new(82.0, 50.0),
+        RADIUS,
+        Vec2::new(20.0 / PHYSICS_DT, 20.0 / PHYSICS_DT),
+        PHYSICS_DT,
+    );
+    assert_eq!(sweep.contacts.len(), 1);
+    assert!(sweep.velocity.x < 0.0 && sweep.velocity.y < 0.0);
+    assert!((sweep.velocity.x - sweep.velocity.y).abs() < 1e-6);
+    assert!(!overlaps_wall(&level, sweep.center));
+}
+
+#[test]
+fn corner_hits_deflect_at_an_angle() {
+    let level = level(PILLAR);
+    // Rolling right, 2 pixels above the line of the pillar's top face, so
+    // the ball meets the rounded top left corner partway round.
+    let sweep = move_circle(
+        &level.map.tile_grid,
+        Vec2::new(84.0, 62.0),
+        RADIUS,
+        Vec2::new(20.0 / PHYSICS_DT, 0.0),
+        PHYSICS_DT,
+    );
+    assert_eq!(sweep.contacts.len(), 1);
+    let normal = sweep.contacts[0].normal;
+    assert!((normal.length() - 1.0).abs() < 1e-9);
+    // Touches at 30 degrees above the corner's leftward normal.
+    assert!((normal.x + 3f64.sqrt() / 2.0).abs() < 1e-6, "{normal:?}");
+    assert!((normal.y + 0.5).abs() < 1e-6, "{normal:?}");
+    // Sent back left and up, not just flipped on one axis.
+    assert!(sweep.velocity.x < 0.0 && sweep.velocity.y < 0.0);
+    assert!(!overlaps_wall(&level, sweep.center));
+}
+
+#[test]
+fn clipping_a_corner_lets_the_ball_carry_on_past_it() {
+    let level = level(PILLAR);
+    // Passes 3.5 pixels above the pillar, so only the edge of the ball
+    // touches it and it keeps going right.
+    let sweep = move_circle(
+        &level.map.tile_grid,
+        Vec2::new(84.0, 60.5),
+        RADIUS,
+        Vec2::new(20.0 / PHYSICS_DT, 0.0),
+        PHYSICS_DT,
+    );
+    assert_eq!(sweep.contacts.len(), 1);
+    assert!(sweep.velocity.x > 0.0 && sweep.velocity.y < 0.0);
+}
+
+#[test]
+fn grazing_a_corner_is_not_a_hit() {
+    let level = level(PILLAR);
+    // Passes just above the pillar's top edge.
+    let sweep = move_circle(
+        &level.map.tile_grid,
+        Vec2::new(80.0, 59.9),
+        RADIUS,
+        Vec2::new(40.0 / PHYSICS_DT, 0.0),
+        PHYSICS_DT,
+    );
+    assert!(sweep.contacts.is_empty());
+}
+
+#[test]
+fn high_power_shots_never_enter_a_wall() {
+    for source in [THIN_WALL, PILLAR] {
+        for step in 0..32 {
+            let mut game = GameState::from_level(level(source));
+            game.ball.angle.theta = step as f64 * PI / 16.0;
+            game.ball.power.power = 10.0;
+            let sim = simulate(game, &[hit()], 1500);
+            let level = level(source);
+            for p in sim.positions() {
+                assert!(!overlaps_wall(&level, p), "angle {step}: {p:?}");
+            }
+        }
+    }
+}
+
+#[test]
+fn very_fast_balls_never_tunnel() {
+    let level = level(THIN_WALL);
+    let mut game = GameState::from_level(level.clone());
+    for step in 0..16 {
+        let theta = step as f64 * PI / 8.0 + 0.1;
+        game.restart_hole();
+        game.ball
+            .velocity
+            .update(Vec2::new(theta.cos(), theta.sin()).scale(20_000.0));
+        game.state = GolfState::Rolling;
+        let sim = simulate(game.clone(), &[], 200);
+        for p in sim.positions() {
+            assert!(!overlaps_wall(&level, p), "{p:?}");
+            assert!(p.x < 80.0, "went through the wall: {p:?}");
+        }
+    }
+}
+
+/// A room with its inside corners cut off and rounded, and a ramp in the
+/// middle, solid on its bottom left.
+const SHAPES: &str = "\
+size 12 7
+tee 3 3
+par 3
+restitution 1 1.0
+---
+############
+#u........i#
+#7.......9.#
+#.....1....#
+#.3......1.#
+#j........k#
+############
+";
+
+/// Whether a ball centered on `center` overlaps the solid part of any tile,
+/// checked at points around its edge.
+fn overlaps_shape(level: &Level, center: Vec2<f64>) -> bool {
+    let grid = &level.map.tile_grid;
+    (0..32).any(|step| {
+        let angle = step as f64 * PI / 16.0;
+        let p = center + Vec2::new(angle.cos(), angle.sin()).scale(RADIUS - 1e-3);
+        let (column, row) = ((p.x / 16.0).floor(), (p.y / 16.0).floor());
+        grid.shape_at(column as isize, row as isize).contains(
+            p.x - column * 16.0,
+            p.y - row * 16.0,
+            16.0,
+        )
+    })
+}
+
+#[test]
+fn diagonal_walls_bounce_off_the_slope() {
+    let level = level(SHAPES);
+    // Rolling left into the ramp at (6, 3), whose slope faces up and right.
+    let sweep = move_circle(
+        &level.map.tile_grid,
+        Vec2::new(124.0, 56.0),
+        RADIUS,
+        Vec2::new(-20.0 / PHYSICS_DT, 0.0),
+        PHYSICS_DT,
+    );
+    assert_eq!(sweep.contacts.len(), 1);
+    let normal = sweep.contacts[0].normal;
+    let expected = Vec2::new(1.0, -1.0).scale(std::f64::consts::FRAC_1_SQRT_2);
+    assert!((normal - expected).length() < 1e-9, "{normal:?}");
+    // A perfectly bouncy slope turns the ball straight up.
+    let velocity = sweep.velocity.scale(PHYSICS_DT);
+    assert!(
+        (velocity - Vec2::new(0.0, -20.0)).length() < 1e-6,
+        "{velocity:?}"
+    );
+}
+
+#[test]
+fn curved_corners_turn_the_ball_along_the_curve() {
+    let level = level(SHAPES);
+    // Rolling up into the rounded top left corner, left of its middle. The
+    // hollow is centered on (32, 32) and the ball touches it 12 pixels out.
+    let sweep = move_circle(
+        &level.map.tile_grid,
+        Vec2::new(24.0, 34.0),
+        RADIUS,
+        Vec2::new(0.0, -20.0 / PHYSICS_DT),
+        PHYSICS_DT,
+    );
+    assert_eq!(sweep.contacts.len(), 1);
+    let normal = sweep.contacts[0].normal;
+    let touch = Vec2::new(-8.0, -(80f64.sqrt()));
+    assert!(
+        (normal - touch.scale(-1.0 / 12.0)).length() < 1e-9,
+        "{normal:?}"
+    );
+    // Turned to run right, along the curve, rather than straight back down.
+    assert!(
+        sweep.velocity.x > sweep.velocity.y.abs(),
+        "{:?}",
+        sweep.velocity
+    );
+    assert!(!overlaps_shape(&level, sweep.center));
+}
+
+/// An open room with a round pillar in it, made of four rounded corners.
+const ROUND_PILLAR: &str = "\
+size 12 9
+tee 2 2
+par 3
+---
+############
+#..........#
+#..........#
+#....KJ....#
+#....IU....#
+#..........#
+#..........#
+#..........#
+############
+";
+
+#[test]
+fn rounded_corners_bounce_off_the_bulge() {
+    let level = level(ROUND_PILLAR);
+    // Rolling down onto the pillar, 6 pixels left of its center at (96, 64).
+    let sweep = move_circle(
+        &level.map.tile_grid,
+        Vec2::new(90.0, 30.0),
+        RADIUS,
+        Vec2::new(0.0, 20.0 / PHYSICS_DT),
+        PHYSICS_DT,
+    );
+    assert_eq!(sweep.contacts.len(), 1);
+    let normal = sweep.contacts[0].normal;
+    let reach = 16.0 + RADIUS;
+    let touch = Vec2::new(-6.0, -(reach * reach - 36.0).sqrt());
+    assert!(
+        (normal - touch.scale(1.0 / reach)).length() < 1e-9,
+        "{normal:?}"
+    );
+    // Glanced off to the left rather than straight back up.
+    assert!(sweep.velocity.x < 0.0, "{:?}", sweep.velocity);
+    assert!(!overlaps_shape(&level, sweep.center));
+}
+
+#[test]
+fn high_power_shots_never_enter_rounded_walls() {
+    let level = level(ROUND_PILLAR);
+    for step in 0..32 {
+        let mut game = GameState::from_level(level.clone());
+        game.ball.angle.theta = step as f64 * PI / 16.0 + 0.05;
+        game.ball.power.power = 10.0;
+        let sim = simulate(game, &[hit()], 1500);
+        for p in sim.positions() {
+            assert!(!overlaps_shape(&level, p), "angle {step}: {p:?}");
+        }
+    }
+}
+
+#[test]
+fn rolling_along_a_curve_stays_clear_of_it() {
+    let level = level(SHAPES);
+    let mut game = GameState::from_level(level.clone());
+    // Straight at the top left corner from the tee.
+    game.ball.angle.theta = 0.6;
+    game.ball.power.power = 10.0;
+    let sim = simulate(game, &[hit()], 600);
+    assert!(sim.events().any(|e| matches!(e, GameEvent::Bounced { .. })));
+    for p in sim.positions() {
+        assert!(!overlaps_shape(&level, p), "{p:?}");
+    }
+}
+
+#[test]
+fn high_power_shots_never_enter_shaped_walls() {
+    let level = level(SHAPES);
+    for step in 0..32 {
+        let mut game = GameState::from_level(level.clone());
+        game.ball.angle.theta = step as f64 * PI / 16.0 + 0.05;
+        game.ball.power.power = 10.0;
+        let sim = simulate(game, &[hit()], 1500);
+        for p in sim.positions() {
+            assert!(!overlaps_shape(&level, p), "angle {step}: {p:?}");
+        }
+    }
+}
@@ -0,0 +1,414 @@
+use line_drawing::Bresenham;
+use paga::common::*;
+use paga::geo::*;
+use paga::render::helpers::*;
+use paga::render::shapes::*;
+use paga::render::sprites::*;
+
+const RED: [u8; 4] = [0xff, 0, 0, 0xff];
+
+fn blank() -> Vec<u8> {
+    vec![0; WIDTH * HEIGHT * 4]
+}
+
+fn pixel(frame: &[u8], x: i64, y: i64) -> [u8; 4] {
+    let i = (x as usize + y as usize * WIDTH) * 4;
+    frame[i..i + 4].try_into().unwrap()
+}
+
+/// Every drawn pixel, in row order.
+fn painted(frame: &[u8]) -> Vec<(i64, i64)> {
+    frame
+        .chunks_exact(4)
+        .enumerate()
+        .filter(|(_, p)| p.iter().any(|&c| c != 0))
+        .map(|(i, _)| ((i % WIDTH) as i64, (i / WIDTH) as i64))
+        .collect()
+}
+
+/// A sprite whose pixel at `x`, `y` is `[x + 1, y + 1, 1, 255]`, so where it
+/// lands shows which part of it was drawn.
+fn gradient(width: usize, height: usize) -> Sprite {
+    let mut pixels = Vec::with_capacity(width * height * 4);
+    for y in 0..height {
+        for x in 0..width {
+            pixels.extend_from_slice(&[x as u8 + 1, y as u8 + 1, 1, 255]);
+        }
+    }
+    Sprite {
+        width,
+        height,
+        pixels,
+    }
+}
+
+fn rect(x1: i64, y1: i64, x2: i64, y2: i64) -> Rect {
+    Rect::new(Vec2::new(x1, y1), Vec2::new(x2, y2))
+}
+
+/// Pixels of `area` and the frame, in row order.
+fn pixels_in(area: Rect) -> Vec<(i64, i64)> {
+    let area = area.intersection(&Rect::screen());
+    let mut pixels = Vec::new();
+    for y in area.p1.y..area.p2.y {
+        for x in area.p1.x..area.p2.x {
+            pixels.push((x, y));
+        }
+    }
+    pixels
+}
+
+/// The pixels of the unclipped line inside `clip`, in row order.
+fn expected_line(clip: &Rect, p1: (i64, i64), p2: (i64, i64)) -> Vec<(i64, i64)> {
+    let area = clip.intersection(&Rect::screen());
+    let mut pixels: Vec<_> = Bresenham::new(p1, p2)
+        .filter(|&(x, y)| area.contains(x, y))
+        .collect();
+    pixels.sort_by_key(|&(x, y)| (y, x));
+    pixels.dedup();
+    pixels
+}
+
+fn assert_blit_lands(dest: (i64, i64), clip: Rect) {
+    let mut frame = blank();
+    let sprite = gradient(8, 6);
+    blit(&mut frame, &clip, dest, &sprite);
+    let expected = pixels_in(rect(dest.0, dest.1, dest.0 + 8, dest.1 + 6).intersection(&clip));
+    assert_eq!(painted(&frame), expected, "{dest:?} {clip:?}");
+    for (x, y) in expected {
+        let (sx, sy) = (x - dest.0, y - dest.1);
+        assert_eq!(
+            pixel(&frame, x, y),
+            [sx as u8 + 1, sy as u8 + 1, 1, 255],
+            "({x}, {y}) from {dest:?}"
+        );
+    }
+}
+
+#[test]
+fn blits_sprites_inside_the_frame() {
+    assert_blit_lands((10, 20), Rect::screen());
+    assert_blit_lands((0, 0), Rect::screen());
+    assert_blit_lands((WIDTH as i64 - 8, HEIGHT as i64 - 6), Rect::screen());
+}
+
+#[test]
+fn clips_sprites_crossing_each_edge() {
+    let screen = Rect::screen();
+    let (w, h) = (WIDTH as i64, HEIGHT as i64);
+    for dest in [
+        (-3, 10),
+        (10, -4),
+        (w - 5, 10),
+        (10, h - 2),
+        (-7, -5),
+        (w - 1, -5),
+        (-7, h - 1),
+        (w - 1, h - 1),
+    ] {
+        assert_blit_lands(dest, screen);
+    }
+}
+
+#[test]
+fn skips_sprites_off_every_side() {
+    let (w, h) = (WIDTH as i64, HEIGHT as i64);
+    for dest in [
+        (-8, 10),
+        (10, -6),
+        (w, 10),
+        (10, h),
+        (-8, -6),
+        (w, h),
+        (i64::MIN / 2, i64::MIN / 2),
+        (i64::MAX / 2, i64::MAX / 2),
+    ] {
+        let mut frame = blank();
+        blit(&mut frame, &Rect::screen(), dest, &gradient(8, 6));
+        assert!(painted(&frame).is_empty(), "{dest:?}");
+    }
+}
+
+#[test]
+fn clips_sprites_larger_than_the_frame() {
+    let mut frame = blank();
+    let sprite = Sprite {
+        width: WIDTH + 20,
+        height: HEIGHT + 20,
+        pixels: vec![7; (WIDTH + 20) * (HEIGHT + 20) * 4],
+    };
+    blit(&mut frame, &Rect::screen(), (-10, -10), &sprite);
+    assert!(frame.iter().all(|&c| c == 7));
+}
+
+#[test]
+fn clips_sprites_to_any_target_rectangle() {
+    let clip = rect(100, 50, 110, 54);
+    for dest in [(98, 48), (105, 52), (101, 51), (95, 45), (111, 50)] {
+        assert_blit_lands(dest, clip);
+    }
+    // A target reaching past the frame is cut to the frame.
+    assert_blit_lands((-4, -4), rect(-100, -100, 3, 3));
+    assert_blit_lands((WIDTH as i64 - 2, 0), rect(0, 0, 10_000, 10_000));
+}
+
+#[test]
+fn draws_nothing_into_an_empty_target() {
+    for clip in [
+        rect(10, 10, 10, 20),
+        rect(10, 10, 20, 10),
+        rect(20, 20, 10, 10),
+        rect(-20, -20, -10, -10),
+        rect(WIDTH as i64, 0, WIDTH as i64 + 10, 10),
+    ] {
+        let mut frame = blank();
+        blit(&mut frame, &clip, (10, 10), &gradient(8, 6));
+        line(&mut frame, &clip, (0, 0), (100, 100), RED);
+        circle(&mut frame, &clip, (12, 12), 5, RED);
+        assert!(painted(&frame).is_empty(), "{clip:?}");
+    }
+}
+
+#[test]
+fn skips_empty_sprites_and_transparent_pixels() {
+    let mut frame = blank();
+    let empty = Sprite {
+        width: 0,
+        height: 0,
+        pixels: Vec::new(),
+    };
+    blit(&mut frame, &Rect::screen(), (10, 10), &empty);
+    assert!(painted(&frame).is_empty());
+
+    frame[..].fill(9);
+    let mut sprite = gradient(2, 1);
+    sprite.pixels[..4].fill(0);
+    blit(&mut frame, &Rect::screen(), (-1, 0), &sprite);
+    blit(&mut frame, &Rect::screen(), (4, 0), &sprite);
+    assert_eq!(pixel(&frame, 0, 0), [2, 1, 1, 255]);
+    assert_eq!(pixel(&frame, 4, 0), [9, 9, 9, 9]);
+    assert_eq!(pixel(&frame, 5, 0), [2, 1, 1, 255]);
+}
+
+#[test]
+fn draws_lines_inside_the_frame() {
+    let mut frame = blank();
+    line(&mut frame, &Rect::screen(), (3, 7), (9, 7), RED);
+    let expected: Vec<_> = (3..=9).map(|x| (x, 7)).collect();
+    assert_eq!(painted(&frame), expected);
+    assert_eq!(pixel(&frame, 3, 7), RED);
+
+    let mut frame = blank();
+    line(&mut frame, &Rect::screen(), (5, 5), (5, 5), RED);
+    assert_eq!(painted(&frame), [(5, 5)]);
+}
+
+#[test]
+fn clips_lines_crossing_the_frame() {
+    let (w, h) = (WIDTH as i64, HEIGHT as i64);
+    for (p1, p2) in [
+        ((-50, 10), (50, 30)),
+        ((10, -50), (30, 50)),
+        ((w - 20, 100), (w + 40, 130)),
+        ((100, h - 10), (140, h + 60)),
+        ((-30, -20), (w + 30, h + 20)),
+        ((w + 30, -20), (-30, h + 20)),
+        ((-1000, 5), (w + 1000, 5)),
+        ((7, -1000), (7, h + 1000)),
+    ] {
+        let mut frame = blank();
+        line(&mut frame, &Rect::screen(), p1, p2, RED);
+        let expected = expected_line(&Rect::screen(), p1, p2);
+        assert!(!expected.is_empty());
+        assert_eq!(painted(&frame), expected, "{p1:?} to {p2:?}");
+    }
+}
+
+#[test]
+fn does_not_smear_lines_off_the_frame_onto_its_border() {
+    let (w, h) = (WIDTH as i64, HEIGHT as i64);
+    for (p1, p2) in [
+        ((-10, -5), (w + 10, -5)),
+        ((w, 0), (w + 50, h)),
+        ((0, h + 1), (w, h + 30)),
+        ((-1, 0), (-1, h)),
+        ((-5, -5), (-5, -5)),
+        ((-100, 50), (-1, 10)),
+    ] {
+        let mut frame = blank();
+        line(&mut frame, &Rect::screen(), p1, p2, RED);
+        assert!(painted(&frame).is_empty(), "{p1:?} to {p2:?}");
+    }
+}
+
+#[test]
+fn clips_lines_to_any_target_rectangle() {
+    let clip = rect(200, 100, 260, 140);
+    for (p1, p2) in [
+        ((150, 90), (300, 160)),
+        ((230, 0), (230, HEIGHT as i64)),
+        ((210, 110), (250, 130)),
+        ((199, 100), (199, 140)),
+        ((260, 100), (300, 140)),
+    ] {
+        let mut frame = blank();
+        line(&mut frame, &clip, p1, p2, RED);
+        assert_eq!(
+            painted(&frame),
+            expected_line(&clip, p1, p2),
+            "{p1:?} {p2:?}"
+        );
+    }
+}
+
+#[test]
+fn matches_the_whole_line_in_every_direction() {
+    // Ends either side of a small target and on its edges, so lines run in
+    // every octant and enter and leave it every way.
+    let clip = rect(30, 20, 45, 31);
+    let (xs, ys) = ([-7, 30, 44, 70], [-7, 20, 30, 60]);
+    // No line reaches further down than its ends, only these rows are read.
+    let rows = WIDTH * 64 * 4;
+    let mut frame = blank();
+    for x1 in xs {
+        for y1 in ys {
+            for x2 in xs {
+                for y2 in ys {
+                    let (p1, p2) = ((x1, y1), (x2, y2));
+                    frame[..rows].fill(0);
+                    line(&mut frame, &clip, p1, p2, RED);
+                    let expected = expected_line(&clip, p1, p2);
+                    assert_eq!(painted(&frame[..rows]), expected, "{p1:?} to {p2:?}");
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn draws_lines_with_far_off_ends() {
+    let (w, h) = (WIDTH as i64, HEIGHT as i64);
+    let (low, high) = (i64::MIN / 2, i64::MAX / 2);
+    let half = high / 2;
+    let cases = [
+        (
+            (-1_000_000_000_000, 5),
+            (5, 5),
+            (0..=5).map(|x| (x, 5)).collect::<Vec<_>>(),
+        ),
+        ((low, 7), (high, 7), (0..w).map(|x| (x, 7)).collect()),
+        ((9, high), (9, low), (0..h).map(|y| (9, y)).collect()),
+        ((low, low), (high, high), (0..h).map(|i| (i, i)).collect()),
+        // Through the origin at half the slope, so x / 2 is where it is.
+        (
+            (-2 * half, -half),
+            (2 * half, half),
+            (0..w).map(|x| (x, x / 2)).collect(),
+        ),
+    ];
+    for (p1, p2, mut expected) in cases {
+        let mut frame = blank();
+        line(&mut frame, &Rect::screen(), p1, p2, RED);
+        expected.sort_by_key(|&(x, y)| (y, x));
+        assert_eq!(painted(&frame), expected, "{p1:?} to {p2:?}");
+    }
+
+    let mut frame = blank();
+    line(
+        &mut frame,
+        &Rect::screen(),
+        (i64::MIN, 3),
+        (i64::MAX, 3),
+        RED,
+    );
+    assert_eq!(painted(&frame).len(), WIDTH);
+
+    // The dots keep their spacing from the far off start.
+    let mut frame = blank();
+    dotted_line(&mut frame, &Rect::screen(), &[(low, 5), (high, 5)], 4, RED);
+    let expected: Vec<_> = (0..w).step_by(4).map(|x| (x, 5)).collect();
+    assert_eq!(painted(&frame), expected);
+}
+
+#[test]
+fn keeps_dot_spacing_when_clipping_dotted_lines() {
+    let clip = rect(20, 0, 60, 10);
+    let mut frame = blank();
+    dotted_line(&mut frame, &clip, &[(-40, 5), (10, 5), (100, 5)], 7, RED);
+    // Dots fall every seventh pixel from the start, whether or not the ones
+    // before the target were drawn.
+    let expected: Vec<_> = (-40 + 7..=100)
+        .step_by(7)
+        .filter(|&x| (20..60).contains(&x))
+        .map(|x| (x, 5))
+        .collect();
+    assert_eq!(painted(&frame), expected);
+}
+
+#[test]
+fn clips_circles_and_outlines() {
+    let mut frame = blank();
+    circle(&mut frame, &Rect::screen(), (-2, -2), 4, RED);
+    let expected: Vec<_> = pixels_in(rect(0, 0, 3, 3))
+        .into_iter()
+        .filter(|&(x, y)| (x + 2) * (x + 2) + (y + 2) * (y + 2) <= 16)
+        .collect();
+    assert_eq!(painted(&frame), expected);
+
+    let mut frame = blank();
+    let clip = rect(10, 10, 20, 20);
+    circle(&mut frame, &clip, (10, 15), 3, RED);
+    assert!(painted(&frame).iter().all(|&(x, y)| clip.contains(x, y)));
+    assert_eq!(pixel(&frame, 13, 15), RED);
+
+    let mut frame = blank();
+    let (w, h) = (WIDTH as i64, HEIGHT as i64);
+    paga::render::helpers::rect(&mut frame, &Rect::screen(), &rect(-5, -5, w + 5, 3), RED);
+    let expected: Vec<_> = (0..w).map(|x| (x, 2)).collect();
+    assert_eq!(painted(&frame), expected);
+    let mut frame = blank();
+    paga::render::helpers::rect(
+        &mut frame,
+        &Rect::screen(),
+        &rect(w - 2, h - 2, w + 9, h + 9),
+        RED,
+    );
+    assert_eq!(
+        painted(&frame),
+        [(w - 2, h - 2), (w - 1, h - 2), (w - 2, h - 1)]
+    );
+}
+
+#[test]
+fn clips_filled_rectangles() {
+    let mut frame = blank();
+    fill_rect(&mut frame, &rect(0, 0, 4, 4), &rect(-3, 2, 2, 9), RED);
+    assert_eq!(painted(&frame), pixels_in(rect(0, 2, 2, 4)));
+
+    let mut frame = blank();
+    fill_rect(&mut frame, &Rect::screen(), &rect(5, 5, 1, 9), RED);
+    assert!(painted(&frame).is_empty());
+}
+
+#[test]
+fn dims_only_inside_the_clip() {
+    let mut frame = blank();
+    fill_rect(&mut frame, &Rect::screen(), &rect(0, 0, 8, 1), RED);
+    dim(&mut frame, &rect(4, 0, 20, 20), &rect(-5, -5, 6, 6), 0.5);
+    assert_eq!(pixel(&frame, 3, 0), RED);
+    assert_eq!(pixel(&frame, 4, 0), [0x80, 0, 0, 0xff]);
+    assert_eq!(pixel(&frame, 5, 0), [0x80, 0, 0, 0xff]);
+    assert_eq!(pixel(&frame, 6, 0), RED);
+}
+
+#[test]
+fn intersects_rectangles() {
+    let a = rect(-10, -10, 10, 10);
+    assert_eq!(a.intersection(&rect(5, -20, 30, 0)), rect(5, -10, 10, 0));
+    assert!(a.intersection(&rect(10, 0, 20, 5)).is_empty());
+    assert!(!a.intersects(&rect(10, 0, 20, 5)));
+    assert!(a.intersects(&rect(9, 9, 20, 20)));
+    assert!(a.contains(-10, 9));
+    assert!(!a.contains(10, 0));
+    assert!(rect(3, 3, 3, 9).is_empty());
+}
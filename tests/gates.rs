@@ -0,0 +1,127 @@
+use paga::entities::tiles::*;
+use paga::game::*;
+use paga::geo::*;
+use paga::map::level::*;
+use paga::sim::*;
+use std::f64::consts::PI;
+
+/// A lane split by a closed gate, with a switch for it between the tee and
+/// the gate.
+const PUZZLE: &str = "\
+size 20 7
+tee 3 3
+par 2
+gate G closed
+switch S G
+oneway > right
+---
+####################
+#.........G........#
+#.........G........#
+#.....S...G........#
+#.........G........#
+#.........G........#
+####################
+";
+
+fn puzzle(level: &str, theta: f64, power: f64) -> GameState {
+    let mut game = GameState::from_level(Level::parse(level).unwrap());
+    game.ball.angle.theta = theta;
+    game.ball.power.power = power;
+    game
+}
+
+fn gate_open(game: &GameState) -> bool {
+    match game.map().tile_grid.tile_at(10, 1).get_type() {
+        TileType::Gate { open, .. } => *open,
+        other => panic!("{other:?} is not a gate"),
+    }
+}
+
+fn furthest_right(sim: &Simulation) -> f64 {
+    sim.positions().map(|p| p.x).fold(0.0, f64::max)
+}
+
+#[test]
+fn closed_gates_are_walls() {
+    let level = PUZZLE.replace("#.....S", "#......");
+    let sim = simulate(puzzle(&level, PI, 4.0), &[hit()], 600);
+    assert!(furthest_right(&sim) < 160.0);
+    assert!(!gate_open(&sim.state));
+}
+
+#[test]
+fn switches_open_their_gates() {
+    let sim = simulate(puzzle(PUZZLE, PI, 4.0), &[hit()], 600);
+    let switched: Vec<_> = sim
+        .events()
+        .filter(|e| matches!(e, GameEvent::Switched { .. }))
+        .collect();
+    assert_eq!(switched, [&GameEvent::Switched { column: 6, row: 3 }]);
+    assert!(gate_open(&sim.state));
+    assert!(furthest_right(&sim) > 176.0);
+}
+
+#[test]
+fn restarting_the_hole_closes_the_gates_again() {
+    let sim = simulate(puzzle(PUZZLE, PI, 4.0), &[hit()], 600);
+    let mut game = sim.state;
+    assert!(gate_open(&game));
+    game.restart_hole();
+    assert!(!gate_open(&game));
+    // The course's own copy of the hole was never changed.
+    assert!(!matches!(
+        game.course.map().tile_grid.tile_at(10, 1).get_type(),
+        TileType::Gate { open: true, .. }
+    ));
+}
+
+#[test]
+fn gates_are_part_of_the_game_state() {
+    // Stop the shot halfway, once the switch has been flipped.
+    let first = simulate(puzzle(PUZZLE, PI, 4.0), &[hit()], 60);
+    assert!(gate_open(&first.state));
+    let saved = first.state.clone();
+    let resumed = simulate(saved, &[], 540);
+    let replayed = simulate(puzzle(PUZZLE, PI, 4.0), &[hit()], 600);
+    assert_eq!(gate_open(&resumed.state), gate_open(&replayed.state));
+    assert_eq!(
+        resumed.state.ball.exact_center(),
+        replayed.state.ball.exact_center()
+    );
+}
+
+#[test]
+fn one_way_walls_only_let_the_ball_through_one_way() {
+    let level = PUZZLE.replace("#.....S", "#......").replace(".G.", ".>.");
+    let sim = simulate(puzzle(&level, PI, 4.0), &[hit()], 600);
+    assert!(furthest_right(&sim) > 176.0);
+
+    let back = level.replace("tee 3 3", "tee 15 3");
+    let sim = simulate(puzzle(&back, 0.0, 4.0), &[hit()], 600);
+    let leftmost = sim.positions().map(|p| p.x).fold(f64::MAX, f64::min);
+    assert!(leftmost > 176.0 - 1e-6, "{leftmost}");
+    let bounce = sim.events().find_map(|e| match e {
+        GameEvent::Bounced { normal } => Some(*normal),
+        _ => None,
+    });
+    assert_eq!(bounce, Some(Vec2::new(1.0, 0.0)));
+}
+
+#[test]
+fn gate_and_switch_lines_are_checked() {
+    let error = Level::parse(&PUZZLE.replace("switch S G", "switch S G >")).unwrap_err();
+    assert_eq!((error.line, error.column), (5, 12));
+    assert_eq!(error.kind, ParseErrorKind::NotAGate('>'));
+
+    let error = Level::parse(&PUZZLE.replace("G closed", "G shut")).unwrap_err();
+    assert_eq!(error.kind, ParseErrorKind::UnexpectedValue("shut".into()));
+
+    let error = Level::parse(&PUZZLE.replace("tee 3 3", "tee 10 3")).unwrap_err();
+    assert_eq!(error.kind, ParseErrorKind::TeeOnWall);
+
+    let open = PUZZLE
+        .replace("G closed", "G open")
+        .replace("tee 3 3", "tee 10 3");
+    assert!(Level::parse(&open).is_ok());
+}
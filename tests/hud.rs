@@ -0,0 +1,94 @@
+use paga::common::*;
+use paga::controls::*;
+use paga::entities::ball::*;
+use paga::game::*;
+use paga::game_info::*;
+use paga::map::level::*;
+use std::f64::consts::PI;
+
+const POWER_COLOR: [u8; 4] = [0xff, 0xc4, 0x00, 0xff];
+
+/// A straight lane with the tee on the left and the hole on the right.
+const LANE: &str = "\
+size 8 3
+tee 1 1
+par 3
+---
+########
+#.....O#
+########
+";
+
+fn lane() -> GameState {
+    GameState::from_level(Level::parse(LANE).unwrap())
+}
+
+fn pixel(frame: &[u8], x: i64, y: i64) -> [u8; 4] {
+    let i = (x as usize + y as usize * WIDTH) * 4;
+    frame[i..i + 4].try_into().unwrap()
+}
+
+/// Columns of the power bar filled in.
+fn power_filled(info: &InfoScreen) -> usize {
+    let mut frame = vec![0; WIDTH * HEIGHT * 4];
+    info.draw(&mut frame);
+    let y = (POWER_BAR.p1.y + POWER_BAR.p2.y) / 2;
+    (POWER_BAR.p1.x..POWER_BAR.p2.x)
+        .filter(|&x| pixel(&frame, x, y) == POWER_COLOR)
+        .count()
+}
+
+#[test]
+fn shows_the_hole_par_and_strokes() {
+    let mut game = lane();
+    let info = InfoScreen::from_game(&game);
+    assert_eq!(
+        (info.hole, info.holes, info.par, info.strokes),
+        (1, 1, 3, 0)
+    );
+
+    game.update(&Controls {
+        hit: true,
+        ..Controls::default()
+    });
+    // The stroke counts once the shot is played on the next update.
+    game.update(&Controls::default());
+    assert_eq!(InfoScreen::from_game(&game).strokes, 1);
+}
+
+#[test]
+fn reads_the_angle_counterclockwise_from_right() {
+    let mut game = lane();
+    for (theta, degrees) in [(PI, 0), (PI / 2.0, 90), (0.0, 180), (1.5 * PI, 270)] {
+        game.ball.angle.theta = theta;
+        assert_eq!(InfoScreen::from_game(&game).angle, degrees, "{theta}");
+    }
+}
+
+#[test]
+fn fills_the_power_bar_between_least_and_most_power() {
+    let mut game = lane();
+    game.ball.power.power = POWER_MIN;
+    let least = InfoScreen::from_game(&game);
+    assert_eq!(least.power, 0.0);
+    assert_eq!(power_filled(&least), 0);
+
+    game.ball.power.power = POWER_MAX;
+    let most = InfoScreen::from_game(&game);
+    assert_eq!(most.power, 1.0);
+    let full = power_filled(&most);
+    assert!(full > 100, "{full}");
+
+    game.ball.power.power = (POWER_MIN + POWER_MAX) / 2.0;
+    let half = power_filled(&InfoScreen::from_game(&game));
+    assert!(half.abs_diff(full / 2) <= 1, "{half} of {full}");
+}
+
+#[test]
+fn draws_only_below_the_view() {
+    let mut frame = vec![0; WIDTH * HEIGHT * 4];
+    InfoScreen::from_game(&lane()).draw(&mut frame);
+    let (view, strip) = frame.split_at(WIDTH * VIEW_HEIGHT * 4);
+    assert!(view.iter().all(|&c| c == 0));
+    assert!(strip.chunks_exact(4).all(|p| p[3] == 0xff));
+}
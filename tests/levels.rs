@@ -0,0 +1,96 @@
+use paga::map::level::*;
+
+const ROOM: &str = "\
+size 6 4
+tee 1 1
+par 2
+---
+######
+#....#
+#....#
+######
+";
+
+/// The same room with a portal at each end of the top row.
+const PORTALS: &str = "\
+size 6 4
+tee 2 1
+par 2
+---
+######
+#@..@#
+#....#
+######
+";
+
+fn error_at(source: &str) -> (usize, usize, ParseErrorKind) {
+    let error = Level::parse(source).unwrap_err();
+    (error.line, error.column, error.kind)
+}
+
+#[test]
+fn grid_errors_point_at_the_tile() {
+    let unknown = ROOM.replacen("#....#", "#..?.#", 1);
+    assert_eq!(error_at(&unknown), (6, 4, ParseErrorKind::UnknownTile('?')));
+
+    let short = ROOM.replacen("#....#\n#....#", "#....#\n#...#", 1);
+    let kind = ParseErrorKind::RowLength {
+        expected: 6,
+        found: 5,
+    };
+    assert_eq!(error_at(&short), (7, 6, kind));
+
+    let long = ROOM.replacen("#....#", "#.....#", 1);
+    let kind = ParseErrorKind::RowLength {
+        expected: 6,
+        found: 7,
+    };
+    assert_eq!(error_at(&long), (6, 7, kind));
+
+    let few = ROOM.strip_suffix("######\n").unwrap();
+    let kind = ParseErrorKind::RowCount {
+        expected: 4,
+        found: 3,
+    };
+    assert_eq!(error_at(few), (8, 1, kind));
+
+    let many = format!("{ROOM}######\n");
+    let kind = ParseErrorKind::RowCount {
+        expected: 4,
+        found: 5,
+    };
+    assert_eq!(error_at(&many), (9, 1, kind));
+}
+
+#[test]
+fn header_errors_point_at_the_key() {
+    let no_grid = "size 6 4\ntee 1 1\npar 2\n";
+    assert_eq!(error_at(no_grid), (4, 1, ParseErrorKind::MissingGrid));
+
+    let no_par = ROOM.replace("par 2\n", "");
+    assert_eq!(error_at(&no_par), (3, 1, ParseErrorKind::MissingKey("par")));
+
+    let twice = ROOM.replace("par 2\n", "par 2\npar 3\n");
+    assert_eq!(
+        error_at(&twice),
+        (4, 1, ParseErrorKind::DuplicateKey("par"))
+    );
+}
+
+#[test]
+fn portal_errors_point_at_the_coordinate() {
+    let with = |links: &str| PORTALS.replace("par 2\n", &format!("par 2\n{links}"));
+    assert!(Level::parse(&with("portal 1,1 4,1\n")).is_ok());
+
+    let kind = ParseErrorKind::NotAPortal { column: 2, row: 1 };
+    assert_eq!(error_at(&with("portal 1,1 2,1\n")), (4, 12, kind));
+
+    let again = with("portal 1,1 4,1\nportal 4,1 1,1\n");
+    let kind = ParseErrorKind::PortalAlreadyLinked { column: 4, row: 1 };
+    assert_eq!(error_at(&again), (5, 8, kind));
+
+    let kind = ParseErrorKind::InvalidCoordinate("1;1".into());
+    assert_eq!(error_at(&with("portal 1;1 4,1\n")), (4, 8, kind));
+
+    assert_eq!(error_at(PORTALS), (6, 2, ParseErrorKind::UnlinkedPortal));
+}
@@ -0,0 +1,248 @@
+use paga::common::*;
+use paga::entities::ball::*;
+use paga::entities::obstacles::*;
+use paga::game::*;
+use paga::geo::*;
+use paga::map::level::*;
+use paga::sim::*;
+use std::f64::consts::PI;
+
+/// An empty room with the tee on the left. Each test adds its obstacles.
+const ROOM: &str = "\
+size 20 7
+tee 4 3
+par 2
+---
+####################
+#..................#
+#..................#
+#..................#
+#..................#
+#..................#
+####################
+";
+
+fn room(obstacles: &str) -> GameState {
+    let level = ROOM.replace("---\n", &format!("{obstacles}---\n"));
+    GameState::from_level(Level::parse(&level).unwrap())
+}
+
+fn bounces(sim: &Simulation) -> Vec<Vec2<f64>> {
+    sim.events()
+        .filter_map(|e| match e {
+            GameEvent::Bounced { normal } => Some(*normal),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn tracks_move_between_keyframes_and_loop() {
+    let mut track = Track::default();
+    for (time, x, degrees) in [(0.0, 0.0, 0.0), (1.0, 32.0, 90.0), (2.0, 0.0, 90.0)] {
+        track.push(Keyframe {
+            time,
+            offset: Vec2::new(x, 0.0),
+            angle: f64::to_radians(degrees),
+        });
+    }
+    let kind = ObstacleKind::Bar { length: 32.0 };
+    let mut bar = Obstacle::new(kind, Vec2::new(100.0, 50.0), track);
+    bar.advance(0.5);
+    let pose = bar.pose();
+    assert_eq!(pose.center, Vec2::new(116.0, 50.0));
+    assert!((pose.angle - PI / 4.0).abs() < 1e-9);
+    assert_eq!(pose.velocity, Vec2::new(32.0, 0.0));
+    assert!((pose.spin - PI / 2.0).abs() < 1e-9);
+
+    bar.advance(1.0);
+    let pose = bar.pose();
+    assert_eq!(pose.center, Vec2::new(116.0, 50.0));
+    assert_eq!(pose.velocity, Vec2::new(-32.0, 0.0));
+    assert_eq!(pose.spin, 0.0);
+
+    // Two seconds later it is at the same point of the next loop.
+    bar.advance(2.0);
+    assert_eq!(bar.pose().center, Vec2::new(116.0, 50.0));
+}
+
+#[test]
+fn still_obstacles_block_the_ball() {
+    let mut game = room("block 12,3 1 3\n");
+    game.ball.angle.theta = PI;
+    game.ball.power.power = 4.0;
+    let sim = simulate(game, &[hit()], 600);
+    let bounces = bounces(&sim);
+    assert!(!bounces.is_empty());
+    assert!((bounces[0].x + 1.0).abs() < 1e-9, "{bounces:?}");
+    // The block's left side is at 192, the ball never got past it.
+    assert!(sim.positions().all(|p| p.x < 192.0 - 4.0 + 1e-6));
+    assert!(sim.state.ball.exact_center().x < 150.0);
+}
+
+#[test]
+fn boosted_balls_never_pass_through_a_bar() {
+    // Six booster patches speed the ball up towards a bar standing across
+    // the whole room, its middle at x = 248.
+    let level = "\
+size 20 7
+tee 1 3
+par 2
+booster > right
+bar 15,3 5
+key 0 0,0 90
+---
+####################
+#..................#
+#..................#
+#.>.>.>.>.>.>......#
+#..................#
+#..................#
+####################
+";
+    for shot in 0..40 {
+        let mut game = GameState::from_level(Level::parse(level).unwrap());
+        game.ball.angle.theta = PI + (shot as f64 - 20.0) * 0.005;
+        game.ball.power.power = POWER_MAX;
+        let sim = simulate(game, &[hit()], 600);
+        let fastest = sim
+            .ticks
+            .windows(2)
+            .map(|w| (w[1].position - w[0].position).length() * 120.0)
+            .fold(0.0, f64::max);
+        assert!(fastest > 1000.0, "shot {shot}: {fastest}");
+        for p in sim.positions() {
+            assert!(p.x < 248.0 - 2.0 - 4.0 + 1e-6, "shot {shot}: {p:?}");
+        }
+    }
+}
+
+#[test]
+fn moving_obstacles_knock_a_resting_ball() {
+    // Slides four tiles left and back every two seconds.
+    let game = room("block 8,3 1 1\nkey 0 0,0 0\nkey 1 -4,0 0\nkey 2 0,0 0\n");
+    let start = game.ball.exact_center();
+    let sim = simulate(game, &[], 240);
+    let knock = sim
+        .first_tick_with(|e| matches!(e, GameEvent::Bounced { .. }))
+        .expect("the block should reach the ball");
+    assert_eq!(knock.state, GolfState::Rolling);
+    let after = &sim.ticks[knock.tick + 1];
+    // Pushed left faster than the block moves, the block's speed passed on.
+    let speed = (after.position.x - knock.position.x) * 120.0;
+    assert!(speed < -64.0, "{speed}");
+    assert!(sim.state.ball.exact_center().x < start.x);
+    assert_eq!(sim.state.strokes(), 0);
+}
+
+#[test]
+fn spinning_blades_throw_the_ball() {
+    // Half a turn a second, the blades pass the ball at about 50 px/s.
+    let game = room("windmill 5,3 4 2\nkey 0 0,0 0\nkey 2 0,0 360\n");
+    let sim = simulate(game, &[], 480);
+    assert!(sim
+        .first_tick_with(|e| matches!(e, GameEvent::Bounced { .. }))
+        .is_some());
+    let fastest = sim
+        .ticks
+        .windows(2)
+        .map(|pair| (pair[1].position - pair[0].position).length() * 120.0)
+        .fold(0.0, f64::max);
+    assert!(fastest > 30.0, "{fastest}");
+}
+
+#[test]
+fn the_preview_plays_obstacles_forward() {
+    let mut game = room("windmill 10,3 4 2\nkey 0 0,0 0\nkey 2 0,0 360\n");
+    game.ball.angle.theta = PI;
+    game.ball.power.power = 5.0;
+    let path = game
+        .ball
+        .aim_path(game.map(), &game.obstacles, &AimPreview::new(600, 10));
+    let sim = simulate(game, &[hit()], 700);
+    assert!(!bounces(&sim).is_empty());
+    let rolled: Vec<_> = sim.positions().skip(1).collect();
+    assert!(path.points().len() > 60);
+    for (i, point) in path.points().iter().enumerate() {
+        assert_eq!(*point, rolled[i], "tick {i}");
+    }
+}
+
+#[test]
+fn restarting_puts_obstacles_back() {
+    let mut game = room("bar 12,3 3\nkey 0 0,0 0\nkey 3 0,0 180\n");
+    for _ in 0..100 {
+        game.update(&Default::default());
+    }
+    assert!(game.obstacles[0].time() > 0.0);
+    game.restart_hole();
+    assert_eq!(game.obstacles[0].time(), 0.0);
+    assert_eq!(game.obstacles, game.map().obstacles);
+}
+
+#[test]
+fn obstacle_lines_are_checked() {
+    let parse =
+        |obstacles: &str| Level::parse(&ROOM.replace("---\n", &format!("{obstacles}---\n")));
+    let error = parse("key 0 0,0 0\n").unwrap_err();
+    assert_eq!((error.line, error.column), (4, 1));
+    assert_eq!(error.kind, ParseErrorKind::KeyframeWithoutObstacle);
+
+    let error = parse("bar 5,5 2\nkey 1 0,0 0\nkey 1 2,0 0\n").unwrap_err();
+    assert_eq!((error.line, error.column), (6, 5));
+    assert_eq!(error.kind, ParseErrorKind::KeyframeOutOfOrder);
+
+    for angle in ["NaN", "inf", "-inf"] {
+        let error = parse(&format!("bar 5,5 2\nkey 1 0,0 {angle}\n")).unwrap_err();
+        assert_eq!((error.line, error.column), (5, 11));
+        assert_eq!(error.kind, ParseErrorKind::InvalidNumber(angle.into()));
+    }
+
+    let error = parse("windmill 5,5 9 2\n").unwrap_err();
+    assert_eq!((error.line, error.column), (4, 14));
+    assert!(matches!(error.kind, ParseErrorKind::OutOfRange { .. }));
+
+    let error = parse("block 25,5 1 1\n").unwrap_err();
+    assert_eq!(
+        error.kind,
+        ParseErrorKind::OutOfBounds { column: 25, row: 5 }
+    );
+
+    // Keyframes have to keep the obstacle on the map.
+    let error = parse("bar 5,5 2\nkey 0 0,0 0\nkey 1 1e300,0 0\n").unwrap_err();
+    assert_eq!((error.line, error.column), (6, 7));
+    let kind = ParseErrorKind::OutOfRange {
+        value: 1e300,
+        min: -5.5,
+        max: 14.5,
+    };
+    assert_eq!(error.kind, kind);
+    let error = parse("bar 5,5 2\nkey 0 0,2 0\n").unwrap_err();
+    assert_eq!((error.line, error.column), (5, 7));
+    let kind = ParseErrorKind::OutOfRange {
+        value: 2.0,
+        min: -5.5,
+        max: 1.5,
+    };
+    assert_eq!(error.kind, kind);
+
+    let level = parse("block 5,5 2 1\nkey 0 -1.5,0 0\nkey 2 0,-2 45\n").unwrap();
+    let track = &level.map.obstacles[0].track;
+    assert_eq!(track.keyframes()[0].offset, Vec2::new(-24.0, 0.0));
+    assert_eq!(track.keyframes()[1].offset, Vec2::new(0.0, -32.0));
+}
+
+#[test]
+fn obstacles_far_off_the_map_still_draw() {
+    let mut game = room("");
+    for anchor in [Vec2::new(1e300, -1e300), Vec2::new(-1e18, 1e18)] {
+        let windmill = ObstacleKind::Windmill {
+            blades: 3,
+            length: 32.0,
+        };
+        game.obstacles
+            .push(Obstacle::new(windmill, anchor, Track::default()));
+    }
+    let mut frame = vec![0; WIDTH * HEIGHT * 4];
+    game.draw(&mut frame, 1.0);
+}
@@ -0,0 +1,72 @@
+use paga::entities::ball::*;
+use paga::game::*;
+use paga::map::level::*;
+use paga::sim::*;
+use std::f64::consts::PI;
+
+/// A long room with the tee on the left.
+const ROOM: &str = "\
+size 20 5
+tee 10 2
+par 2
+---
+####################
+#..................#
+#..................#
+#..................#
+####################
+";
+
+fn room(theta: f64, power: f64) -> GameState {
+    let mut game = GameState::from_level(Level::parse(ROOM).unwrap());
+    game.ball.angle.theta = theta;
+    game.ball.power.power = power;
+    game
+}
+
+#[test]
+fn preview_follows_the_real_shot() {
+    // Banked off the left wall and back across the room.
+    let game = room(0.2, 8.0);
+    let path = game
+        .ball
+        .aim_path(game.map(), &game.obstacles, &AimPreview::new(10 * 120, 3));
+    let sim = simulate(game, &[hit()], 2000);
+    assert!(sim.events().any(|e| matches!(e, GameEvent::Bounced { .. })));
+    // The shot is taken on the second tick and the ball first moves on the third.
+    let rolled: Vec<_> = sim.positions().skip(1).collect();
+    assert!(path.points().len() > 100);
+    for (i, point) in path.points().iter().enumerate() {
+        assert_eq!(*point, rolled[i], "tick {i}");
+    }
+}
+
+#[test]
+fn preview_ends_at_the_bounce_limit() {
+    let game = room(0.0, 6.0);
+    let path = game
+        .ball
+        .aim_path(game.map(), &game.obstacles, &AimPreview::new(1000, 0));
+    let sim = simulate(game.clone(), &[hit()], 1000);
+    let bounce = sim
+        .first_tick_with(|e| matches!(e, GameEvent::Bounced { .. }))
+        .unwrap();
+    assert_eq!(path.points().last(), Some(&bounce.position));
+    let longer = game
+        .ball
+        .aim_path(game.map(), &game.obstacles, &AimPreview::new(1000, 1));
+    assert!(longer.points().len() > path.points().len());
+}
+
+#[test]
+fn preview_is_limited_to_max_ticks() {
+    let game = room(PI, 6.0);
+    let path = game
+        .ball
+        .aim_path(game.map(), &game.obstacles, &AimPreview::new(10, 2));
+    assert_eq!(path.points().len(), 11);
+    let path = game
+        .ball
+        .aim_path(game.map(), &game.obstacles, &AimPreview::off());
+    assert!(path.points().is_empty());
+}
@@ -0,0 +1,265 @@
+use paga::common::*;
+use paga::course::*;
+use paga::entities::ball::*;
+use paga::game::*;
+use paga::map::level::*;
+use paga::screens::game_screen::*;
+use paga::screens::menus::*;
+use paga::screens::scenes::*;
+use std::f64::consts::PI;
+
+/// A straight lane with the tee on the left and the hole on the right.
+const LANE: &str = "\
+size 8 3
+tee 1 1
+par 2
+---
+########
+#.....O#
+########
+";
+
+fn manager() -> SceneManager {
+    SceneManager::new(Course::new(vec![Level::parse(LANE).unwrap()]), false)
+}
+
+fn playing(scenes: &mut SceneManager) -> &mut GameScreen {
+    match &mut scenes.screen {
+        Screen::Game(screen) => screen,
+        _ => panic!("not playing"),
+    }
+}
+
+fn is_start(scenes: &SceneManager) -> bool {
+    matches!(scenes.screen, Screen::Start(_))
+}
+
+/// Starts a round and lines up a shot that sinks.
+fn started() -> SceneManager {
+    let mut scenes = manager();
+    scenes.press(MenuKey::Select);
+    let screen = playing(&mut scenes);
+    screen.game.ball.angle.theta = PI;
+    screen.game.ball.power.power = 2.0;
+    scenes
+}
+
+#[test]
+fn opens_on_the_start_menu() {
+    let scenes = manager();
+    assert!(is_start(&scenes));
+    assert!(!scenes.should_quit());
+}
+
+#[test]
+fn start_plays_the_first_hole() {
+    let mut scenes = manager();
+    scenes.press(MenuKey::Select);
+    let screen = playing(&mut scenes);
+    assert!(!screen.paused);
+    assert_eq!(screen.game.course.current_index(), 0);
+    assert_eq!(screen.game.strokes(), 0);
+}
+
+#[test]
+fn menus_wrap_around() {
+    let mut menu = Menu::new(vec![
+        MenuOptions::Start,
+        MenuOptions::Restart,
+        MenuOptions::Quit,
+    ]);
+    assert_eq!(menu.selected(), MenuOptions::Start);
+    menu.press(MenuKey::Up);
+    assert_eq!(menu.selected(), MenuOptions::Quit);
+    menu.press(MenuKey::Down);
+    menu.press(MenuKey::Down);
+    assert_eq!(menu.selected(), MenuOptions::Restart);
+    assert_eq!(menu.press(MenuKey::Select), Some(MenuOptions::Restart));
+    assert_eq!(menu.press(MenuKey::Back), None);
+}
+
+#[test]
+fn quitting_from_the_start_menu_closes_the_game() {
+    let mut scenes = manager();
+    scenes.press(MenuKey::Down);
+    scenes.press(MenuKey::Select);
+    assert!(scenes.should_quit());
+
+    let mut scenes = manager();
+    scenes.press(MenuKey::Back);
+    assert!(scenes.should_quit());
+}
+
+#[test]
+fn pausing_freezes_the_game() {
+    let mut scenes = started();
+    playing(&mut scenes).controls.hit = true;
+    for _ in 0..10 {
+        scenes.update();
+    }
+    scenes.press(MenuKey::Back);
+    let frozen = playing(&mut scenes).game.ball.exact_center();
+    assert!(playing(&mut scenes).paused);
+    for _ in 0..100 {
+        scenes.update();
+    }
+    assert_eq!(playing(&mut scenes).game.ball.exact_center(), frozen);
+    assert_eq!(playing(&mut scenes).game.state, GolfState::Rolling);
+
+    scenes.press(MenuKey::Pause);
+    assert!(!playing(&mut scenes).paused);
+    scenes.update();
+    assert_ne!(playing(&mut scenes).game.ball.exact_center(), frozen);
+}
+
+#[test]
+fn resuming_plays_on_from_where_it_paused() {
+    let mut scenes = started();
+    playing(&mut scenes).controls.hit = true;
+    for _ in 0..10 {
+        scenes.update();
+    }
+    scenes.press(MenuKey::Pause);
+    let frozen = playing(&mut scenes).game.ball.exact_center();
+    for _ in 0..100 {
+        scenes.update();
+    }
+    assert_eq!(
+        playing(&mut scenes).pause_menu.selected(),
+        MenuOptions::Resume
+    );
+    scenes.press(MenuKey::Select);
+    let screen = playing(&mut scenes);
+    assert!(!screen.paused);
+    assert_eq!(screen.game.state, GolfState::Rolling);
+    assert_eq!(screen.game.strokes(), 1);
+    assert_eq!(screen.game.ball.exact_center(), frozen);
+}
+
+#[test]
+fn settings_cycle_the_aim_guide_and_stay_paused() {
+    let mut scenes = started();
+    scenes.press(MenuKey::Pause);
+    scenes.press(MenuKey::Down);
+    scenes.press(MenuKey::Down);
+    let mut seen = Vec::new();
+    for _ in 0..AIM_PREVIEWS.len() {
+        scenes.press(MenuKey::Select);
+        let screen = playing(&mut scenes);
+        assert!(screen.paused);
+        seen.push(screen.game.preview.name());
+    }
+    assert_eq!(seen, ["LONG", "OFF", "SHORT", "NORMAL"]);
+    assert_eq!(AimPreview::new(7, 7).name(), "CUSTOM");
+    assert_eq!(AimPreview::new(7, 7).next(), AIM_PREVIEWS[0].1);
+}
+
+#[test]
+fn pausing_dims_the_game_behind_the_menu() {
+    let mut scenes = started();
+    let mut playing_frame = vec![0; WIDTH * HEIGHT * 4];
+    scenes.draw(&mut playing_frame, 0.0);
+    scenes.press(MenuKey::Pause);
+    let mut paused_frame = vec![0; WIDTH * HEIGHT * 4];
+    scenes.draw(&mut paused_frame, 0.0);
+
+    // The top left corner is clear of the menu and its text.
+    let i = 4 * (2 + 2 * WIDTH);
+    let (before, after) = (&playing_frame[i..i + 4], &paused_frame[i..i + 4]);
+    assert!(before[..3].iter().any(|&c| c > 0));
+    assert!((0..3).all(|c| after[c] < before[c]), "{before:?} {after:?}");
+}
+
+#[test]
+fn a_paused_ball_holds_still_between_frames() {
+    // A lane along the top of a hole as tall as the view, so the ball is
+    // clear of the pause menu.
+    let tall = LANE.replace("size 8 3", "size 8 23").replace(
+        "#.....O#\n########",
+        &format!("#.....O#{}\n########", "\n#......#".repeat(20)),
+    );
+    let course = Course::new(vec![Level::parse(&tall).unwrap()]);
+    let mut scenes = SceneManager::new(course, false);
+    scenes.press(MenuKey::Select);
+    let screen = playing(&mut scenes);
+    screen.game.ball.angle.theta = PI;
+    screen.game.ball.power.power = 2.0;
+    screen.controls.hit = true;
+    for _ in 0..10 {
+        scenes.update();
+    }
+    scenes.press(MenuKey::Pause);
+    scenes.update();
+    let mut early = vec![0; WIDTH * HEIGHT * 4];
+    let mut late = vec![0; WIDTH * HEIGHT * 4];
+    scenes.draw(&mut early, 0.0);
+    scenes.draw(&mut late, 1.0);
+    assert!(early == late);
+}
+
+#[test]
+fn restarting_from_the_pause_menu_replays_the_hole() {
+    let mut scenes = started();
+    playing(&mut scenes).controls.hit = true;
+    for _ in 0..10 {
+        scenes.update();
+    }
+    scenes.press(MenuKey::Pause);
+    scenes.press(MenuKey::Down);
+    scenes.press(MenuKey::Select);
+    let screen = playing(&mut scenes);
+    assert!(!screen.paused);
+    assert_eq!(screen.game.strokes(), 0);
+    assert_eq!(screen.game.state, GolfState::Aiming);
+}
+
+#[test]
+fn quitting_from_the_pause_menu_goes_back_to_the_start() {
+    let mut scenes = started();
+    scenes.press(MenuKey::Pause);
+    scenes.press(MenuKey::Up);
+    assert_eq!(
+        playing(&mut scenes).pause_menu.selected(),
+        MenuOptions::QuitToMenu
+    );
+    scenes.press(MenuKey::Select);
+    assert!(is_start(&scenes));
+    assert!(!scenes.should_quit());
+}
+
+#[test]
+fn sinking_the_last_hole_shows_the_end_screen() {
+    let mut scenes = started();
+    playing(&mut scenes).controls.hit = true;
+    for _ in 0..2000 {
+        scenes.update();
+        if matches!(scenes.screen, Screen::End(_)) {
+            break;
+        }
+    }
+    assert!(matches!(scenes.screen, Screen::End(_)));
+
+    scenes.press(MenuKey::Select);
+    assert!(is_start(&scenes));
+    scenes.press(MenuKey::Select);
+    let screen = playing(&mut scenes);
+    assert_eq!(screen.game.strokes(), 0);
+    assert_eq!(screen.game.state, GolfState::Aiming);
+}
+
+#[test]
+fn draws_every_screen() {
+    let mut frame = vec![0; WIDTH * HEIGHT * 4];
+    let mut scenes = started();
+    scenes.draw(&mut frame, 0.5);
+    scenes.press(MenuKey::Pause);
+    scenes.draw(&mut frame, 0.5);
+    scenes.press(MenuKey::Up);
+    scenes.press(MenuKey::Select);
+    scenes.draw(&mut frame, 0.5);
+    let mut scenes = started();
+    playing(&mut scenes).game.state = GolfState::Finished;
+    scenes.update();
+    assert!(matches!(scenes.screen, Screen::End(_)));
+    scenes.draw(&mut frame, 0.5);
+}
@@ -0,0 +1,44 @@
+use paga::scoring::*;
+
+#[test]
+fn results_are_named_against_par() {
+    assert_eq!(ScoreName::from_strokes(1, 3), ScoreName::HoleInOne);
+    // A hole in one beats the under-par names, even on a par five.
+    assert_eq!(ScoreName::from_strokes(1, 5), ScoreName::HoleInOne);
+    assert_eq!(ScoreName::from_strokes(3, 5), ScoreName::Eagle);
+    assert_eq!(ScoreName::from_strokes(3, 4), ScoreName::Birdie);
+    assert_eq!(ScoreName::from_strokes(4, 4), ScoreName::Par);
+    assert_eq!(ScoreName::from_strokes(5, 4), ScoreName::Bogey);
+    assert_eq!(ScoreName::from_strokes(7, 4), ScoreName::TripleBogey);
+    assert_eq!(ScoreName::from_strokes(8, 4), ScoreName::Over(4));
+    assert_eq!(ScoreName::from_strokes(12, 3), ScoreName::Over(9));
+    assert_eq!(ScoreName::Over(4).to_string(), "+4");
+}
+
+#[test]
+fn scorecard_totals_every_hole() {
+    let mut card = Scorecard::new(&[3, 4, 5]);
+    assert_eq!((card.total_strokes(), card.total_par()), (0, 12));
+    card.add_stroke(0);
+    card.add_stroke(0);
+    card.complete(0);
+    card.add_stroke(1);
+    card.add_penalty(1);
+    card.add_stroke(1);
+    assert_eq!(card.total_strokes(), 5);
+    assert_eq!(card.hole(1).unwrap().penalties, 1);
+    // Only completed holes count towards par.
+    assert_eq!(card.to_par(), -1);
+    assert_eq!(card.hole(0).unwrap().name(), Some(ScoreName::Birdie));
+    assert_eq!(card.hole(1).unwrap().name(), None);
+
+    card.reset_hole(1);
+    assert_eq!(*card.hole(1).unwrap(), HoleScore::new(4));
+    assert_eq!(card.total_strokes(), 2);
+    assert!(card.hole(0).unwrap().completed);
+
+    card.reset();
+    assert_eq!(card.holes(), Scorecard::new(&[3, 4, 5]).holes());
+    assert_eq!((card.total_strokes(), card.total_par()), (0, 12));
+    assert!(!card.is_complete());
+}
@@ -0,0 +1,389 @@
+use paga::common::*;
+use paga::course::*;
+use paga::entities::portals::*;
+use paga::entities::tiles::*;
+use paga::game::*;
+use paga::map::level::*;
+use paga::sim::*;
+use std::f64::consts::PI;
+
+/// A straight lane with the tee on the left and the hole on the right.
+const LANE: &str = "\
+size 8 3
+tee 1 1
+par 2
+---
+########
+#.....O#
+########
+";
+
+fn lane(theta: f64, power: f64) -> GameState {
+    let mut game = GameState::from_level(Level::parse(LANE).unwrap());
+    game.ball.angle.theta = theta;
+    game.ball.power.power = power;
+    game
+}
+
+#[test]
+fn idle_game_stays_on_the_tee() {
+    let game = lane(PI, 2.0);
+    let start = game.ball.exact_center();
+    let sim = simulate(game, &[], 100);
+    assert!(sim.positions().all(|p| p == start));
+    assert!(sim.ticks.iter().all(|r| r.state == GolfState::Aiming));
+    assert_eq!(sim.events().count(), 0);
+}
+
+#[test]
+fn hitting_counts_a_stroke() {
+    let sim = simulate(lane(PI, 2.0), &[hit()], 2);
+    assert_eq!(
+        sim.events().collect::<Vec<_>>(),
+        vec![&GameEvent::Hit { hole: 0, stroke: 1 }]
+    );
+    assert_eq!(sim.ticks[1].state, GolfState::Rolling);
+    assert_eq!(sim.state.strokes(), 1);
+}
+
+#[test]
+fn rolling_into_the_hole_finishes_a_one_hole_course() {
+    let sim = simulate(lane(PI, 2.0), &[hit()], 2000);
+    let events: Vec<_> = sim.events().cloned().collect();
+    assert_eq!(
+        events,
+        vec![
+            GameEvent::Hit { hole: 0, stroke: 1 },
+            GameEvent::Sunk {
+                hole: 0,
+                strokes: 1
+            },
+            GameEvent::CourseFinished,
+        ]
+    );
+    assert_eq!(sim.state.state, GolfState::Finished);
+    assert!(sim.state.scorecard.is_complete());
+    assert_eq!(sim.state.scorecard.to_par(), -1);
+}
+
+#[test]
+fn sinking_a_hole_tees_up_the_next_one() {
+    let second = Level::parse(&LANE.replace("tee 1 1", "tee 2 1")).unwrap();
+    let second_tee = GameState::from_level(second.clone()).ball.exact_center();
+    let course = Course::new(vec![Level::parse(LANE).unwrap(), second]);
+    let mut game = GameState::from_course(course);
+    game.ball.angle.theta = PI;
+    game.ball.power.power = 2.0;
+    let sim = simulate_with(game, 4000, |_, game| {
+        if game.state == GolfState::Aiming {
+            hit()
+        } else {
+            Default::default()
+        }
+    });
+    let started = sim
+        .first_tick_with(|e| matches!(e, GameEvent::HoleStarted { .. }))
+        .expect("the second hole should start");
+    assert_eq!(started.events, vec![GameEvent::HoleStarted { hole: 1 }]);
+    assert_eq!(started.position, second_tee);
+    assert_eq!(started.state, GolfState::Aiming);
+
+    // The course only finishes once the last hole is sunk.
+    let events: Vec<_> = sim.events().cloned().collect();
+    assert_eq!(
+        events,
+        vec![
+            GameEvent::Hit { hole: 0, stroke: 1 },
+            GameEvent::Sunk {
+                hole: 0,
+                strokes: 1
+            },
+            GameEvent::HoleStarted { hole: 1 },
+            GameEvent::Hit { hole: 1, stroke: 1 },
+            GameEvent::Sunk {
+                hole: 1,
+                strokes: 1
+            },
+            GameEvent::CourseFinished,
+        ]
+    );
+    assert_eq!(sim.state.state, GolfState::Finished);
+    assert!(sim.state.scorecard.is_complete());
+}
+
+#[test]
+fn ball_stops_and_can_be_hit_again() {
+    // Hit away from the hole, into the left wall, softly.
+    let sim = simulate_with(lane(0.0, 1.0), 4000, |_, game| {
+        if game.state == GolfState::Aiming && game.strokes() < 2 {
+            hit()
+        } else {
+            Default::default()
+        }
+    });
+    let stops = sim
+        .events()
+        .filter(|e| matches!(e, GameEvent::Stopped { .. }))
+        .count();
+    assert_eq!(stops, 2);
+    assert_eq!(sim.state.strokes(), 2);
+    assert_eq!(sim.state.state, GolfState::Aiming);
+}
+
+#[test]
+fn ball_stays_inside_the_map() {
+    let mut game = GameState::new();
+    game.ball.power.power = 10.0;
+    let (width, height) = (game.map().width as f64, game.map().height as f64);
+    let sim = simulate_with(game, 6000, |_, game| {
+        if game.state == GolfState::Aiming {
+            hit()
+        } else {
+            Default::default()
+        }
+    });
+    for p in sim.positions() {
+        assert!(
+            p.x > 0.0 && p.x < width && p.y > 0.0 && p.y < height,
+            "{p:?}"
+        );
+    }
+}
+
+#[test]
+fn linked_portals_teleport_the_ball() {
+    let level = "\
+size 9 5
+tee 1 1
+par 3
+portal 4,1 4,3:left
+---
+#########
+#...@...#
+#########
+#...@...#
+#########
+";
+    let mut game = GameState::from_level(Level::parse(level).unwrap());
+    game.ball.angle.theta = PI;
+    game.ball.power.power = 2.0;
+    let sim = simulate(game, &[hit()], 3000);
+    let teleport = sim
+        .first_tick_with(|e| matches!(e, GameEvent::Teleported { .. }))
+        .expect("ball should go through the portal");
+    assert!(teleport.position.y > 48.0);
+    // The exit faces left, so the ball carries on to the left.
+    let after = sim.ticks[teleport.tick + 10].position;
+    assert!(after.x < teleport.position.x);
+    // It has to leave the exit portal before it can be sent back.
+    let next = sim.ticks[teleport.tick + 1..].iter().find(|r| {
+        r.events
+            .iter()
+            .any(|e| matches!(e, GameEvent::Teleported { .. }))
+    });
+    if let Some(next) = next {
+        assert!(next.tick > teleport.tick + PORTAL_COOLDOWN_TICKS as usize);
+    }
+}
+
+#[test]
+fn portals_keep_the_speed_and_turn_it_to_the_exit() {
+    let level = "\
+size 9 7
+tee 1 1
+par 3
+portal 4,1 6,3:down
+---
+#########
+#...@...#
+#########
+#.....@.#
+#.......#
+#.......#
+#########
+";
+    let mut game = GameState::from_level(Level::parse(level).unwrap());
+    game.ball.angle.theta = PI;
+    game.ball.power.power = 2.0;
+    let sim = simulate(game, &[hit()], 3000);
+    let teleport = sim
+        .first_tick_with(|e| matches!(e, GameEvent::Teleported { .. }))
+        .expect("ball should go through the portal");
+    let t = teleport.tick;
+    let before = sim.ticks[t - 1].position - sim.ticks[t - 2].position;
+    let after = sim.ticks[t + 2].position - sim.ticks[t + 1].position;
+    assert!(before.x > 0.0 && before.y.abs() < 1e-9, "{before:?}");
+    // Same speed, now pointing down out of the exit.
+    assert!(after.x.abs() < 1e-9 && after.y > 0.0, "{after:?}");
+    assert!((after.y - before.x).abs() < before.x * 0.05);
+    // Landing on the exit portal does not send the ball straight back.
+    for record in &sim.ticks[t + 1..=t + PORTAL_COOLDOWN_TICKS as usize] {
+        assert!(record.events.is_empty(), "{:?}", record.events);
+        assert!(record.position.y > 48.0);
+    }
+}
+
+#[test]
+fn friction_is_measured_per_second() {
+    // The hit lands on the second update, then the ball rolls for one second.
+    let sim = simulate(lane(PI, 2.0), &[hit()], 2 + PHYSICS_HZ as usize);
+    let friction = sim.state.map().tile_grid.tile_at(2, 1).friction();
+    let launch = 2.0 / 4.0 * sim.state.ball.power.speed;
+    let speed = sim.state.ball.velocity.vec2().length();
+    assert!((speed - launch * friction).abs() < 0.01, "{speed}");
+    let travelled = sim.state.ball.exact_center().x - 24.0;
+    let expected = launch * (friction - 1.0) / friction.ln();
+    assert!((travelled - expected).abs() < 1.0, "{travelled} {expected}");
+}
+
+#[test]
+fn rough_ground_stops_the_ball_sooner() {
+    let rough = LANE.replace("par 2\n", "par 2\nfriction . 0.3\n");
+    let rolled = |level: &str| {
+        let mut game = GameState::from_level(Level::parse(level).unwrap());
+        game.ball.angle.theta = 0.0;
+        game.ball.power.power = 4.0;
+        let sim = simulate(game, &[hit()], 3000);
+        let bounced = sim
+            .events()
+            .filter(|e| matches!(e, GameEvent::Bounced { .. }))
+            .count();
+        (sim.state.ball.exact_center().x, bounced)
+    };
+    let (normal_x, normal_bounces) = rolled(LANE);
+    let (rough_x, rough_bounces) = rolled(&rough);
+    assert_eq!((normal_bounces, rough_bounces), (1, 1));
+    // Both bounce off the left wall, the rough lane leaves the ball closer to it.
+    assert!(rough_x < normal_x, "{rough_x} {normal_x}");
+}
+
+#[test]
+fn bouncy_walls_return_more_speed() {
+    let bouncy = LANE.replace("par 2\n", "par 2\nrestitution # 1.0\n");
+    let speed_after_bounce = |level: &str| {
+        let mut game = GameState::from_level(Level::parse(level).unwrap());
+        game.ball.angle.theta = 0.0;
+        game.ball.power.power = 4.0;
+        let sim = simulate(game, &[hit()], 200);
+        let bounce = sim
+            .first_tick_with(|e| matches!(e, GameEvent::Bounced { .. }))
+            .expect("ball should reach the wall")
+            .tick;
+        let after = sim.ticks[bounce + 1].position.x - sim.ticks[bounce].position.x;
+        let before = sim.ticks[bounce - 1].position.x - sim.ticks[bounce - 2].position.x;
+        after / -before
+    };
+    let normal = speed_after_bounce(LANE);
+    let bouncy = speed_after_bounce(&bouncy);
+    assert!((bouncy - 1.0).abs() < 0.05, "{bouncy}");
+    assert!(normal < 0.85, "{normal}");
+}
+
+#[test]
+fn water_costs_a_stroke_and_returns_the_ball() {
+    let pond = LANE.replace("#.....O#", "#...~.O#");
+    let mut game = GameState::from_level(Level::parse(&pond).unwrap());
+    game.ball.angle.theta = PI;
+    game.ball.power.power = 2.0;
+    let tee = game.ball.exact_center();
+    let sim = simulate(game, &[hit()], 600);
+    let lost: Vec<_> = sim
+        .events()
+        .filter(|e| matches!(e, GameEvent::BallLost { .. }))
+        .collect();
+    assert_eq!(
+        lost,
+        vec![&GameEvent::BallLost {
+            hazard: Hazard::Tile(TileType::Water),
+            returned_to: tee,
+        }]
+    );
+    assert_eq!(sim.state.state, GolfState::Aiming);
+    assert_eq!(sim.state.ball.exact_center(), tee);
+    let score = sim.state.scorecard.hole(0).unwrap();
+    assert_eq!((score.strokes, score.penalties), (2, 1));
+}
+
+#[test]
+fn sand_stops_the_ball_sooner() {
+    let bunker = LANE.replace("#.....O#", "#.::::O#");
+    let rolled = |level: &str| {
+        let mut game = GameState::from_level(Level::parse(level).unwrap());
+        game.ball.angle.theta = PI;
+        game.ball.power.power = 1.0;
+        let sim = simulate(game, &[hit()], 3000);
+        sim.state.ball.exact_center().x
+    };
+    let sand_x = rolled(&bunker);
+    assert!(sand_x < rolled(LANE), "{sand_x}");
+}
+
+#[test]
+fn tee_in_a_hazard_is_rejected() {
+    let level = LANE.replace("#.....O#", "#~....O#");
+    let error = Level::parse(&level).unwrap_err();
+    assert_eq!(error.kind, ParseErrorKind::TeeInHazard);
+}
+
+/// An open field for gravity wells, the ball shot right along row 2.
+const FIELD: &str = "\
+size 20 9
+tee 2 2
+par 3
+---
+####################
+#..................#
+#..................#
+#..................#
+#..................#
+#..................#
+#..................#
+#..................#
+####################
+";
+
+fn field(extra: &str) -> GameState {
+    let level = FIELD.replace("par 3\n", &format!("par 3\n{extra}\n"));
+    let mut game = GameState::from_level(Level::parse(&level).unwrap());
+    game.ball.angle.theta = PI;
+    game.ball.power.power = 6.0;
+    game
+}
+
+#[test]
+fn planets_bend_the_ball_towards_them() {
+    let straight = simulate(field(""), &[hit()], 120);
+    let bent = simulate(field("planet 10,5 2000 1"), &[hit()], 120);
+    let start_y = straight.ticks[0].position.y;
+    assert!(straight.positions().all(|p| (p.y - start_y).abs() < 1e-6));
+    let end = bent.state.ball.exact_center();
+    assert!(end.y > start_y + 4.0, "{end:?}");
+}
+
+#[test]
+fn black_holes_swallow_the_ball() {
+    let sim = simulate(field("blackhole 12,2 200 1"), &[hit()], 600);
+    let lost = sim
+        .first_tick_with(|e| matches!(e, GameEvent::BallLost { .. }))
+        .expect("ball should fall into the black hole");
+    assert!(lost.events.contains(&GameEvent::BallLost {
+        hazard: Hazard::BlackHole,
+        returned_to: sim.ticks[0].position,
+    }));
+    assert_eq!(sim.state.strokes(), 2);
+}
+
+#[test]
+fn gravity_wells_are_checked_when_parsing() {
+    let heavy = FIELD.replace("par 3\n", "par 3\nplanet 4,4 200000 1\n");
+    let error = Level::parse(&heavy).unwrap_err();
+    assert_eq!((error.line, error.column), (4, 12));
+    assert!(matches!(error.kind, ParseErrorKind::OutOfRange { .. }));
+
+    let outside = FIELD.replace("par 3\n", "par 3\nblackhole 25,4 200 1\n");
+    let error = Level::parse(&outside).unwrap_err();
+    assert_eq!(
+        error.kind,
+        ParseErrorKind::OutOfBounds { column: 25, row: 4 }
+    );
+}
@@ -0,0 +1,180 @@
+use paga::entities::ball::*;
+use paga::entities::tiles::*;
+use paga::game::*;
+use paga::geo::*;
+use paga::map::level::*;
+use paga::sim::*;
+use std::f64::consts::PI;
+
+/// A lane with a slope running down to the left from column 3 to 8, and
+/// flat ground either side. `<` is given its strength by each test.
+const HILL: &str = "\
+size 14 5
+tee 6 2
+par 2
+---
+##############
+#............#
+#..<<<<<<....#
+#............#
+##############
+";
+
+fn hill(strength: u8, tee: usize, theta: f64, power: f64) -> GameState {
+    let level = HILL
+        .replace("par 2\n", &format!("par 2\nslope < left {strength}\n"))
+        .replace("tee 6 2", &format!("tee {tee} 2"));
+    let mut game = GameState::from_level(Level::parse(&level).unwrap());
+    game.ball.angle.theta = theta;
+    game.ball.power.power = power;
+    game
+}
+
+fn on_slope(game: &GameState, at: Vec2<f64>) -> bool {
+    let tile = game
+        .map()
+        .tile_grid
+        .tile_at(at.x as usize >> 4, at.y as usize >> 4);
+    matches!(tile.get_type(), TileType::Slope { .. })
+}
+
+fn stops(sim: &Simulation) -> Vec<Vec2<f64>> {
+    sim.events()
+        .filter_map(|e| match e {
+            GameEvent::Stopped { at } => Some(*at),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn balls_roll_back_down_a_steep_slope() {
+    // Up the slope and not far enough to reach the top.
+    let game = hill(3, 6, PI, 1.5);
+    let start = game.ball.exact_center();
+    let sim = simulate(game.clone(), &[hit()], 30 * 120);
+    let stops = stops(&sim);
+    assert_eq!(stops.len(), 1, "{stops:?}");
+    assert!(!on_slope(&game, stops[0]), "{:?}", stops[0]);
+    // It went up first, then came back down past where it started.
+    assert!(sim.positions().any(|p| p.x > start.x + 4.0));
+    assert!(stops[0].x < start.x);
+}
+
+#[test]
+fn balls_can_rest_on_a_gentle_slope() {
+    let game = hill(1, 6, PI, 1.5);
+    let sim = simulate(game.clone(), &[hit()], 30 * 120);
+    let stops = stops(&sim);
+    assert_eq!(stops.len(), 1, "{stops:?}");
+    assert!(on_slope(&game, stops[0]), "{:?}", stops[0]);
+}
+
+#[test]
+fn slopes_speed_the_ball_up_downhill() {
+    let rolled = |strength| {
+        let sim = simulate(hill(strength, 6, 0.0, 2.0), &[hit()], 40);
+        sim.state.ball.velocity.x()
+    };
+    let flat = {
+        let level = Level::parse(&HILL.replace('<', ".")).unwrap();
+        let mut game = GameState::from_level(level);
+        game.ball.angle.theta = 0.0;
+        game.ball.power.power = 2.0;
+        simulate(game, &[hit()], 40).state.ball.velocity.x()
+    };
+    // Moving left, so faster is more negative.
+    assert!(rolled(1) < flat);
+    assert!(rolled(3) < rolled(1));
+}
+
+#[test]
+fn a_wall_at_the_bottom_of_a_slope_holds_the_ball() {
+    let level = "\
+size 14 5
+tee 9 2
+par 2
+slope < left 3
+---
+##############
+#............#
+#<<<<<<......#
+#............#
+##############
+";
+    let mut game = GameState::from_level(Level::parse(level).unwrap());
+    game.ball.angle.theta = 0.0;
+    game.ball.power.power = 2.0;
+    let sim = simulate(game, &[hit()], 60 * 120);
+    let stops = stops(&sim);
+    assert_eq!(stops.len(), 1, "{stops:?}");
+    assert!((stops[0].x - 20.0).abs() < 1.0, "{:?}", stops[0]);
+}
+
+#[test]
+fn the_preview_curves_down_across_a_slope() {
+    // Shot straight across a slope running down the screen.
+    let level = "\
+size 14 7
+tee 1 1
+par 2
+slope v down 2
+---
+##############
+#....vvvv....#
+#....vvvv....#
+#....vvvv....#
+#....vvvv....#
+#............#
+##############
+";
+    let mut game = GameState::from_level(Level::parse(level).unwrap());
+    game.ball.angle.theta = PI;
+    game.ball.power.power = 4.0;
+    let start = game.ball.exact_center();
+    let path = game
+        .ball
+        .aim_path(game.map(), &game.obstacles, &AimPreview::new(120, 0));
+    let points = path.points();
+    let first_on = points.iter().position(|p| on_slope(&game, *p));
+    let first_on = first_on.expect("the preview should reach the slope");
+    let last_on = points.iter().rposition(|p| on_slope(&game, *p)).unwrap();
+    assert!(last_on - first_on > 10, "{first_on}..{last_on}");
+    // Level before the slope, pushed further downhill the longer it is on it.
+    assert!(points[..first_on]
+        .iter()
+        .all(|p| (p.y - start.y).abs() < 0.5));
+    let drop = |i: usize| points[i].y - start.y;
+    assert!(drop(last_on) > 4.0, "{}", drop(last_on));
+    assert!(drop(last_on) > 2.0 * drop((first_on + last_on) / 2));
+
+    // It is the path the shot really takes.
+    let sim = simulate(game, &[hit()], 200);
+    let rolled: Vec<_> = sim.positions().skip(1).collect();
+    for (i, point) in points.iter().enumerate() {
+        assert_eq!(*point, rolled[i], "tick {i}");
+    }
+}
+
+#[test]
+fn slope_lines_are_checked() {
+    let taken = HILL.replace("par 2\n", "par 2\nslope # left 2\n");
+    let error = Level::parse(&taken).unwrap_err();
+    assert_eq!((error.line, error.column), (4, 7));
+    assert_eq!(error.kind, ParseErrorKind::SymbolInUse('#'));
+
+    let steep = HILL.replace("par 2\n", "par 2\nslope < left 4\n");
+    let error = Level::parse(&steep).unwrap_err();
+    assert_eq!((error.line, error.column), (4, 14));
+    assert!(matches!(error.kind, ParseErrorKind::OutOfRange { .. }));
+
+    let sideways = HILL.replace("par 2\n", "par 2\nslope < sideways 2\n");
+    let error = Level::parse(&sideways).unwrap_err();
+    assert_eq!(
+        error.kind,
+        ParseErrorKind::InvalidDownhill("sideways".into())
+    );
+
+    let undeclared = Level::parse(HILL).unwrap_err();
+    assert_eq!(undeclared.kind, ParseErrorKind::UnknownTile('<'));
+}
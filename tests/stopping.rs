@@ -0,0 +1,129 @@
+use paga::common::*;
+use paga::entities::ball::*;
+use paga::game::*;
+use paga::geo::*;
+use paga::map::level::*;
+use paga::sim::*;
+use std::f64::consts::PI;
+
+/// An open green with the tee on the left and a hole six tiles to its right.
+const GREEN: &str = "\
+size 12 9
+tee 2 4
+par 2
+---
+############
+#..........#
+#..........#
+#..........#
+#.......O..#
+#..........#
+#..........#
+#..........#
+############
+";
+
+fn green(extra: &str, power: f64, offset: f64) -> GameState {
+    let level = GREEN.replace("par 2\n", &format!("par 2\n{extra}\n"));
+    let mut game = GameState::from_level(Level::parse(&level).unwrap());
+    let tee = game.ball.exact_center();
+    game.ball.place(tee + Vec2::new(0.0, offset));
+    game.ball.angle.theta = PI;
+    game.ball.power.power = power;
+    game
+}
+
+fn lipped_out(sim: &Simulation) -> bool {
+    sim.events()
+        .any(|e| matches!(e, GameEvent::LippedOut { .. }))
+}
+
+fn sunk(sim: &Simulation) -> bool {
+    sim.events().any(|e| matches!(e, GameEvent::Sunk { .. }))
+}
+
+#[test]
+fn straight_shots_roll_until_they_slow_down() {
+    // Straight up, so the sideways speed is exactly zero the whole way.
+    let mut game = green("", 3.0, 0.0);
+    game.ball.angle.theta = PI / 2.0;
+    assert_eq!(game.ball.angle.xv(), 0.0);
+    let sim = simulate(game, &[hit()], 2000);
+    let stop = sim
+        .first_tick_with(|e| matches!(e, GameEvent::Stopped { .. }))
+        .expect("ball should stop");
+    let rolled: f64 = sim.ticks[..=stop.tick]
+        .windows(2)
+        .map(|pair| (pair[1].position - pair[0].position).length())
+        .sum();
+    assert!(rolled > 64.0, "{rolled}");
+    // It crept for a few ticks under the rest speed before stopping.
+    let creeping = &sim.ticks[stop.tick + 1 - REST_TICKS as usize..=stop.tick];
+    for pair in creeping.windows(2) {
+        let moved = (pair[1].position - pair[0].position).length();
+        assert!(moved < REST_SPEED * PHYSICS_DT, "{moved}");
+    }
+    let before = &sim.ticks[stop.tick - REST_TICKS as usize..];
+    assert!((before[1].position - before[0].position).length() >= REST_SPEED * PHYSICS_DT);
+}
+
+#[test]
+fn turning_around_under_gravity_is_not_stopping() {
+    // Hit up and away from a planet, which pulls the ball back down.
+    let mut game = green("planet 5,6 8000 0.5", 6.0, 0.0);
+    game.ball.place(Vec2::new(88.0, 72.0));
+    game.ball.angle.theta = PI / 2.0;
+    let sim = simulate(game, &[hit()], 240);
+    let apex = sim
+        .ticks
+        .iter()
+        .min_by(|a, b| a.position.y.total_cmp(&b.position.y))
+        .unwrap();
+    assert!(apex.position.y < 64.0, "{:?}", apex.position);
+    assert!(!sim.events().any(|e| matches!(e, GameEvent::Stopped { .. })));
+    let fallen = sim.ticks[apex.tick + 30].position.y;
+    assert!(fallen > apex.position.y + 8.0, "{fallen}");
+}
+
+#[test]
+fn slow_balls_drop_in() {
+    let sim = simulate(green("", 3.0, 0.0), &[hit()], 1000);
+    assert!(sunk(&sim));
+    assert!(!lipped_out(&sim));
+}
+
+#[test]
+fn the_middle_of_the_cup_catches_faster_balls() {
+    let middle = simulate(green("", 5.5, 0.0), &[hit()], 1000);
+    assert!(sunk(&middle));
+    assert!(!lipped_out(&middle));
+
+    let edge = simulate(green("", 5.5, 5.0), &[hit()], 1000);
+    assert!(lipped_out(&edge));
+}
+
+#[test]
+fn fast_balls_lip_out_and_are_turned_by_the_rim() {
+    let sim = simulate(green("", 9.0, 5.0), &[hit()], 120);
+    let lip = sim
+        .first_tick_with(|e| matches!(e, GameEvent::LippedOut { .. }))
+        .expect("ball should lip out");
+    let before = sim.ticks[lip.tick].position - sim.ticks[lip.tick - 1].position;
+    let after = sim.ticks[lip.tick + 2].position - sim.ticks[lip.tick + 1].position;
+    // Rolling straight along the row, then turned down, away from the cup.
+    assert!(before.y.abs() < 1e-9);
+    assert!(after.y > 0.5, "{after:?}");
+    assert!(after.length() < before.length());
+    assert_eq!(sim.ticks[lip.tick + 1].state, GolfState::Rolling);
+}
+
+#[test]
+fn fast_balls_straight_over_the_middle_are_not_turned() {
+    let sim = simulate(green("", 10.0, 0.0), &[hit()], 120);
+    let lip = sim
+        .first_tick_with(|e| matches!(e, GameEvent::LippedOut { .. }))
+        .expect("ball should be too fast to drop");
+    let after = sim.ticks[lip.tick + 2].position - sim.ticks[lip.tick + 1].position;
+    assert!(after.y.abs() < 1e-9, "{after:?}");
+    assert!(after.x > 0.0);
+}
@@ -0,0 +1,114 @@
+use paga::common::*;
+use paga::geo::*;
+use paga::render::shapes::*;
+use paga::render::text::*;
+
+const INK: [u8; 4] = [0xff, 0xff, 0xff, 0xff];
+
+fn blank() -> Vec<u8> {
+    vec![0; WIDTH * HEIGHT * 4]
+}
+
+fn area(x1: i64, y1: i64, x2: i64, y2: i64) -> Rect {
+    Rect::new(Vec2::new(x1, y1), Vec2::new(x2, y2))
+}
+
+/// Every drawn pixel, in row order.
+fn painted(frame: &[u8]) -> Vec<(i64, i64)> {
+    frame
+        .chunks_exact(4)
+        .enumerate()
+        .filter(|(_, p)| p.iter().any(|&c| c != 0))
+        .map(|(i, _)| ((i % WIDTH) as i64, (i / WIDTH) as i64))
+        .collect()
+}
+
+/// The pixels of `c` drawn with its top left at `x`, `y`.
+fn glyph_at(c: char, x: i64, y: i64) -> Vec<(i64, i64)> {
+    let mut pixels = Vec::new();
+    for (row, bits) in glyph(c).iter().enumerate() {
+        for column in 0..GLYPH_SIZE {
+            if bits >> column & 1 == 1 {
+                pixels.push((x + column, y + row as i64));
+            }
+        }
+    }
+    pixels
+}
+
+#[test]
+fn draws_glyphs_with_the_lowest_bit_leftmost() {
+    let mut frame = blank();
+    draw_text(&mut frame, &Rect::screen(), "I", INK, Align::Left);
+    // A serifed I: a bar across the top and bottom, a stem in the middle.
+    let mut expected: Vec<(i64, i64)> = (1..5).map(|x| (x, 0)).collect();
+    expected.extend((1..6).map(|y| (2, y)).chain((1..6).map(|y| (3, y))));
+    expected.extend((1..5).map(|x| (x, 6)));
+    expected.sort_by_key(|&(x, y)| (y, x));
+    assert_eq!(painted(&frame), expected);
+}
+
+#[test]
+fn aligns_each_line_within_the_area() {
+    let line = "HI";
+    assert_eq!(text_width(line), 2 * GLYPH_SIZE);
+    for (align, left) in [
+        (Align::Left, 100),
+        (Align::Center, 100 + (80 - 16) / 2),
+        (Align::Right, 180 - 16),
+    ] {
+        let mut frame = blank();
+        draw_text(&mut frame, &area(100, 50, 180, 90), line, INK, align);
+        let mut expected = glyph_at('H', left, 50);
+        expected.extend(glyph_at('I', left + GLYPH_SIZE, 50));
+        expected.sort_by_key(|&(x, y)| (y, x));
+        assert_eq!(painted(&frame), expected, "{align:?}");
+    }
+}
+
+#[test]
+fn wraps_between_words_and_at_newlines() {
+    assert_eq!(wrap("HOLE 3 PAR 4", 6), ["HOLE 3", "PAR 4"]);
+    assert_eq!(wrap("ONE\nTWO THREE", 20), ["ONE", "TWO THREE"]);
+    assert_eq!(wrap("ABCDEFGHIJ", 4), ["ABCD", "EFGH", "IJ"]);
+    assert_eq!(wrap("", 4), [""]);
+
+    let mut frame = blank();
+    draw_text(
+        &mut frame,
+        &area(0, 0, 48, 100),
+        "HOLE 3 PAR 4",
+        INK,
+        Align::Left,
+    );
+    assert!(painted(&frame)
+        .iter()
+        .any(|&(x, y)| y >= LINE_HEIGHT && x < GLYPH_SIZE));
+}
+
+#[test]
+fn clips_to_the_area_and_the_frame() {
+    let mut frame = blank();
+    let inside = area(10, 10, 30, 14);
+    draw_text(&mut frame, &inside, "MMMMMMMM", INK, Align::Left);
+    let drawn = painted(&frame);
+    assert!(!drawn.is_empty());
+    assert!(drawn.iter().all(|&(x, y)| inside.contains(x, y)));
+
+    let mut frame = blank();
+    let past_corner = area(
+        WIDTH as i64 - 4,
+        HEIGHT as i64 - 4,
+        WIDTH as i64 + 40,
+        HEIGHT as i64 + 40,
+    );
+    draw_text(&mut frame, &past_corner, "MMMM", INK, Align::Left);
+    assert!(!painted(&frame).is_empty());
+}
+
+#[test]
+fn draws_unknown_characters_as_question_marks() {
+    assert_eq!(glyph('é'), glyph('?'));
+    assert_eq!(glyph('\t'), glyph('?'));
+    assert!(glyph(' ').iter().all(|&row| row == 0));
+}
@@ -0,0 +1,162 @@
+use paga::entities::ball::*;
+use paga::entities::tiles::*;
+use paga::game::*;
+use paga::map::level::*;
+use paga::physics::*;
+use paga::sim::*;
+use std::f64::consts::PI;
+
+/// A lane with the tee on the left. Tests swap in their tiles for the `|`
+/// column and `+` patch.
+const LANE: &str = "\
+size 20 7
+tee 4 3
+par 2
+booster > right
+booster ^ up
+conveyor = down 1
+---
+####################
+#.........|........#
+#.........|........#
+#.......++|........#
+#.........|........#
+#.........|........#
+####################
+";
+
+fn lane(column: char, patch: char, power: f64) -> GameState {
+    let level = LANE
+        .replace('|', &column.to_string())
+        .replace('+', &patch.to_string());
+    let mut game = GameState::from_level(Level::parse(&level).unwrap());
+    game.ball.angle.theta = PI;
+    game.ball.power.power = power;
+    game
+}
+
+/// Speed in pixels per second between the ticks around `tick`.
+fn speed_at(sim: &Simulation, tick: usize) -> f64 {
+    (sim.ticks[tick + 1].position - sim.ticks[tick].position).length() * 120.0
+}
+
+#[test]
+fn bumpers_throw_the_ball_back_faster() {
+    let sim = simulate(lane('*', '.', 4.0), &[hit()], 300);
+    let bounce = sim
+        .first_tick_with(|e| matches!(e, GameEvent::Bounced { .. }))
+        .unwrap();
+    let before = speed_at(&sim, bounce.tick - 2);
+    let after = speed_at(&sim, bounce.tick + 1);
+    assert!(after > before * 1.3, "{before} -> {after}");
+}
+
+#[test]
+fn bouncing_between_bumpers_stays_under_the_top_speed() {
+    // Bumpers on both ends of a short corridor, each hit speeds the ball up.
+    let level = "\
+size 12 3
+tee 5 1
+par 2
+---
+############
+#*........*#
+############
+";
+    let mut game = GameState::from_level(Level::parse(level).unwrap());
+    game.ball.angle.theta = PI;
+    game.ball.power.power = 10.0;
+    let sim = simulate(game, &[hit()], 30 * 120);
+    let bounces = sim
+        .events()
+        .filter(|e| matches!(e, GameEvent::Bounced { .. }))
+        .count();
+    assert!(bounces > 50, "{bounces}");
+    let fastest = (0..sim.ticks.len() - 1)
+        .map(|tick| speed_at(&sim, tick))
+        .fold(0.0, f64::max);
+    assert!(fastest.is_finite());
+    assert!(fastest <= MAX_BALL_SPEED + 1.0, "{fastest}");
+    assert!(sim.positions().all(|p| p.x > 16.0 && p.x < 176.0));
+}
+
+#[test]
+fn boosters_add_speed_once_per_patch() {
+    let sim = simulate(lane('.', '>', 2.0), &[hit()], 200);
+    // The patch covers x 128..160, the speed is read either side of it.
+    let tick_at = |x: f64| sim.positions().position(|p| p.x > x).unwrap();
+    let before = speed_at(&sim, tick_at(110.0));
+    let after = speed_at(&sim, tick_at(170.0));
+    let gained = after - before;
+    // Friction takes a little back, a second boost would be far more.
+    assert!(
+        gained > BOOST_SPEED * 0.8 && gained < BOOST_SPEED * 1.1,
+        "{gained}"
+    );
+}
+
+#[test]
+fn boosters_push_towards_their_facing() {
+    let sim = simulate(lane('.', '^', 2.0), &[hit()], 240);
+    let entered = sim.positions().position(|p| p.x >= 128.0).unwrap();
+    let (from, to) = (sim.ticks[entered].position, sim.ticks[entered + 1].position);
+    // Sent up the screen while still moving right.
+    assert!(to.y - from.y < -1.0, "{from:?} -> {to:?}");
+    assert!(to.x > from.x);
+}
+
+#[test]
+fn conveyors_carry_the_ball_along() {
+    let sim = simulate(lane('=', '.', 2.5), &[hit()], 20 * 120);
+    let on_belt = |x: f64| (160.0..176.0).contains(&x);
+    let carried = sim
+        .ticks
+        .windows(2)
+        .filter(|pair| on_belt(pair[0].position.x))
+        .map(|pair| (pair[1].position.y - pair[0].position.y) * 120.0)
+        .fold(0.0, f64::max);
+    assert!(carried > 20.0, "{carried}");
+    let stops: Vec<_> = sim
+        .events()
+        .filter_map(|e| match e {
+            GameEvent::Stopped { at } => Some(*at),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(stops.len(), 1);
+    // Never left sitting on the moving belt.
+    assert!(!on_belt(stops[0].x) || stops[0].y > 80.0, "{:?}", stops[0]);
+}
+
+#[test]
+fn the_preview_rolls_over_boosters_and_belts() {
+    let game = lane('=', '>', 2.0);
+    let path = game
+        .ball
+        .aim_path(game.map(), &game.obstacles, &AimPreview::new(600, 4));
+    let sim = simulate(game, &[hit()], 700);
+    let rolled: Vec<_> = sim.positions().skip(1).collect();
+    assert!(path.points().iter().any(|p| p.x > 170.0));
+    for (i, point) in path.points().iter().enumerate() {
+        assert_eq!(*point, rolled[i], "tick {i}");
+    }
+}
+
+#[test]
+fn booster_and_conveyor_lines_are_checked() {
+    let lane = LANE.replace(['|', '+'], ".");
+    let error = Level::parse(&lane.replace("booster ^ up", "booster * up")).unwrap_err();
+    assert_eq!((error.line, error.column), (5, 9));
+    assert_eq!(error.kind, ParseErrorKind::SymbolInUse('*'));
+
+    let error = Level::parse(&lane.replace("down 1", "down 4")).unwrap_err();
+    assert_eq!((error.line, error.column), (6, 17));
+    assert!(matches!(error.kind, ParseErrorKind::OutOfRange { .. }));
+
+    let error = Level::parse(&lane.replace("booster ^ up", "booster ^ up-left")).unwrap_err();
+    assert_eq!(error.kind, ParseErrorKind::InvalidFacing("up-left".into()));
+
+    let bumper_tee = lane.replace("#.........", "#...*.....");
+    let error = Level::parse(&bumper_tee).unwrap_err();
+    assert_eq!(error.kind, ParseErrorKind::TeeOnWall);
+}